@@ -41,7 +41,7 @@ fn print_all_id_variants(shortguid: ShortGuid) {
     let uuid_as_bytes = shortguid.as_bytes();
     let hex_uuid_string = hex::encode(uuid_as_bytes);
     let little_endian_short = shortguid.to_bytes_le();
-    let le_short_uuid = ShortGuid::from_bytes(&little_endian_short);
+    let le_short_uuid = ShortGuid::from_bytes(little_endian_short);
     engine.encode_string(uuid_as_bytes, &mut buffer);
 
     println!("Short UUID:                  {}", shortguid);
@@ -0,0 +1,26 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use shortguid::{bulk, ShortGuid};
+
+fuzz_target!(|data: &[u8]| {
+    let input = &data[..data.len() - data.len() % bulk::ENCODED_LEN];
+    let mut decoded = vec![ShortGuid::new_random(); input.len() / bulk::ENCODED_LEN];
+    let errors = bulk::decode_many(input, &mut decoded).err().unwrap_or_default();
+
+    // The bulk decoder must agree with parsing each item on its own.
+    let mut errors = errors.into_iter();
+    for (i, chars) in input.chunks_exact(bulk::ENCODED_LEN).enumerate() {
+        let expected = std::str::from_utf8(chars)
+            .map_err(|_| ())
+            .and_then(|str| ShortGuid::try_parse(str).map_err(|_| ()));
+        match expected {
+            Ok(id) => assert_eq!(decoded[i], id),
+            Err(()) => {
+                assert!(decoded[i].is_empty());
+                assert_eq!(errors.next().map(|(index, _)| index), Some(i));
+            }
+        }
+    }
+    assert_eq!(errors.next(), None);
+});
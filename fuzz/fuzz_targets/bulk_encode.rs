@@ -0,0 +1,13 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use shortguid::{bulk, ShortGuid};
+
+fuzz_target!(|ids: Vec<ShortGuid>| {
+    let mut encoded = vec![0u8; ids.len() * bulk::ENCODED_LEN];
+    bulk::encode_many(&ids, &mut encoded);
+
+    // The bulk encoder must agree with encoding each ID on its own.
+    let expected: String = ids.iter().map(ShortGuid::to_string).collect();
+    assert_eq!(encoded, expected.as_bytes());
+});
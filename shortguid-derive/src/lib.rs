@@ -0,0 +1,369 @@
+//! # shortguid-derive
+//!
+//! Provides the `ShortGuidNewtype` derive macro for newtypes around
+//! [`ShortGuid`](https://docs.rs/shortguid). Use it through the `derive` feature
+//! of the `shortguid` crate rather than depending on this crate directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Fields, LitStr, Member};
+
+/// Derives the conversion, formatting and comparison traits of `ShortGuid` for a
+/// newtype wrapping a single `ShortGuid` field.
+///
+/// The generated implementations forward to the inner value:
+///
+/// * `Debug`, `Display` and `FromStr`
+/// * `TryFrom<&str>` and `TryFrom<String>`
+/// * `From<ShortGuid>`, `From<Uuid>` and their reverse conversions
+/// * `PartialEq` against `ShortGuid`, `Uuid`, strings and byte slices
+/// * `AsRef<ShortGuid>`, `AsRef<Uuid>`, `AsRef<[u8]>` and `Borrow<Uuid>`
+///
+/// Derivable traits such as `Clone`, `Copy`, `Eq`, `Hash` and `Ord` are left to `#[derive]`.
+///
+/// ## Attributes
+///
+/// * `#[shortguid(prefix = "user")]` - renders and parses the ID as `user_<ShortGuid>`.
+/// * `#[shortguid(serde)]` - implements `Serialize` and `Deserialize`.
+/// * `#[shortguid(borsh)]` - implements `BorshSerialize` and `BorshDeserialize`.
+/// * `#[shortguid(bytemuck)]` - implements `Zeroable`, `Pod` and `TransparentWrapper<ShortGuid>`;
+///   requires `#[repr(transparent)]`.
+///
+/// The integrations require the matching feature of the `shortguid` crate.
+#[proc_macro_derive(ShortGuidNewtype, attributes(shortguid))]
+pub fn derive_short_guid_newtype(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// The options given in `#[shortguid(...)]` attributes.
+#[derive(Default)]
+struct Options {
+    prefix: Option<LitStr>,
+    serde: bool,
+    borsh: bool,
+    bytemuck: bool,
+}
+
+impl Options {
+    fn from_input(input: &DeriveInput) -> syn::Result<Self> {
+        let mut options = Options::default();
+        for attr in input
+            .attrs
+            .iter()
+            .filter(|a| a.path().is_ident("shortguid"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("prefix") {
+                    let prefix: LitStr = meta.value()?.parse()?;
+                    if prefix.value().is_empty() {
+                        return Err(syn::Error::new_spanned(prefix, "prefix must not be empty"));
+                    }
+                    options.prefix = Some(prefix);
+                } else if meta.path.is_ident("serde") {
+                    options.serde = true;
+                } else if meta.path.is_ident("borsh") {
+                    options.borsh = true;
+                } else if meta.path.is_ident("bytemuck") {
+                    options.bytemuck = true;
+                } else {
+                    return Err(meta.error("unsupported shortguid attribute"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(options)
+    }
+}
+
+/// Determines the single field of the newtype.
+fn inner_field(input: &DeriveInput) -> syn::Result<Member> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "ShortGuidNewtype can only be derived for structs",
+            ))
+        }
+    };
+
+    match fields {
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => Ok(Member::from(0)),
+        Fields::Named(fields) if fields.named.len() == 1 => {
+            let ident = fields.named[0].ident.clone().expect("named field");
+            Ok(Member::Named(ident))
+        }
+        _ => Err(syn::Error::new_spanned(
+            &input.ident,
+            "ShortGuidNewtype requires a struct with exactly one ShortGuid field",
+        )),
+    }
+}
+
+fn is_repr_transparent(input: &DeriveInput) -> bool {
+    input
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident("repr"))
+        .any(|a| {
+            let mut transparent = false;
+            let _ = a.parse_nested_meta(|meta| {
+                transparent |= meta.path.is_ident("transparent");
+                Ok(())
+            });
+            transparent
+        })
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let options = Options::from_input(&input)?;
+    let field = inner_field(&input)?;
+
+    let name = &input.ident;
+    let name_str = name.to_string();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let sg = quote!(::shortguid::ShortGuid);
+    let private = quote!(::shortguid::__private);
+    let uuid = quote!(#private::Uuid);
+    let parse_error = quote!(::shortguid::ParseError);
+
+    let construct = match &field {
+        Member::Unnamed(_) => quote!(Self(value)),
+        Member::Named(ident) => quote!(Self { #ident: value }),
+    };
+
+    let (display, parse, eq_str) = match &options.prefix {
+        Some(prefix) => (
+            quote!(#private::fmt_prefixed(f, #prefix, &self.#field)),
+            quote!(#private::parse_prefixed(value, #prefix)),
+            quote!(#private::parse_prefixed(other, #prefix).map_or(false, |id| id == self.#field)),
+        ),
+        None => (
+            quote!(::std::fmt::Display::fmt(&self.#field, f)),
+            quote!(#sg::try_parse(value)),
+            quote!(self.#field == *other),
+        ),
+    };
+
+    let mut tokens = quote! {
+        impl #impl_generics ::std::fmt::Debug for #name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                f.debug_tuple(#name_str).field(&self.#field).finish()
+            }
+        }
+
+        impl #impl_generics ::std::fmt::Display for #name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                #display
+            }
+        }
+
+        impl #impl_generics ::std::str::FromStr for #name #ty_generics #where_clause {
+            type Err = #parse_error;
+
+            fn from_str(value: &str) -> ::std::result::Result<Self, Self::Err> {
+                #parse.map(|value| #construct)
+            }
+        }
+
+        impl #impl_generics ::std::convert::TryFrom<&str> for #name #ty_generics #where_clause {
+            type Error = #parse_error;
+
+            fn try_from(value: &str) -> ::std::result::Result<Self, Self::Error> {
+                ::std::str::FromStr::from_str(value)
+            }
+        }
+
+        impl #impl_generics ::std::convert::TryFrom<::std::string::String> for #name #ty_generics #where_clause {
+            type Error = #parse_error;
+
+            fn try_from(value: ::std::string::String) -> ::std::result::Result<Self, Self::Error> {
+                ::std::str::FromStr::from_str(&value)
+            }
+        }
+
+        impl #impl_generics ::std::convert::From<#sg> for #name #ty_generics #where_clause {
+            fn from(value: #sg) -> Self {
+                #construct
+            }
+        }
+
+        impl #impl_generics ::std::convert::From<#name #ty_generics> for #sg #where_clause {
+            fn from(value: #name #ty_generics) -> Self {
+                value.#field
+            }
+        }
+
+        impl #impl_generics ::std::convert::From<#uuid> for #name #ty_generics #where_clause {
+            fn from(value: #uuid) -> Self {
+                let value = #sg::from(value);
+                #construct
+            }
+        }
+
+        impl #impl_generics ::std::convert::From<#name #ty_generics> for #uuid #where_clause {
+            fn from(value: #name #ty_generics) -> Self {
+                value.#field.into()
+            }
+        }
+
+        impl #impl_generics ::std::cmp::PartialEq<#sg> for #name #ty_generics #where_clause {
+            fn eq(&self, other: &#sg) -> bool {
+                self.#field == *other
+            }
+        }
+
+        impl #impl_generics ::std::cmp::PartialEq<#uuid> for #name #ty_generics #where_clause {
+            fn eq(&self, other: &#uuid) -> bool {
+                self.#field == *other
+            }
+        }
+
+        impl #impl_generics ::std::cmp::PartialEq<str> for #name #ty_generics #where_clause {
+            fn eq(&self, other: &str) -> bool {
+                #eq_str
+            }
+        }
+
+        impl #impl_generics ::std::cmp::PartialEq<&str> for #name #ty_generics #where_clause {
+            fn eq(&self, other: &&str) -> bool {
+                <Self as ::std::cmp::PartialEq<str>>::eq(self, *other)
+            }
+        }
+
+        impl #impl_generics ::std::cmp::PartialEq<::std::string::String> for #name #ty_generics #where_clause {
+            fn eq(&self, other: &::std::string::String) -> bool {
+                <Self as ::std::cmp::PartialEq<str>>::eq(self, other.as_str())
+            }
+        }
+
+        impl #impl_generics ::std::cmp::PartialEq<::std::vec::Vec<u8>> for #name #ty_generics #where_clause {
+            fn eq(&self, other: &::std::vec::Vec<u8>) -> bool {
+                self.#field == *other
+            }
+        }
+
+        impl #impl_generics ::std::cmp::PartialEq<&[u8]> for #name #ty_generics #where_clause {
+            fn eq(&self, other: &&[u8]) -> bool {
+                self.#field == *other
+            }
+        }
+
+        impl #impl_generics ::std::cmp::PartialEq<&[u8; 16]> for #name #ty_generics #where_clause {
+            fn eq(&self, other: &&[u8; 16]) -> bool {
+                self.#field == *other
+            }
+        }
+
+        impl #impl_generics ::std::cmp::PartialEq<[u8; 16]> for #name #ty_generics #where_clause {
+            fn eq(&self, other: &[u8; 16]) -> bool {
+                self.#field == *other
+            }
+        }
+
+        impl #impl_generics ::std::convert::AsRef<#sg> for #name #ty_generics #where_clause {
+            fn as_ref(&self) -> &#sg {
+                &self.#field
+            }
+        }
+
+        impl #impl_generics ::std::convert::AsRef<#uuid> for #name #ty_generics #where_clause {
+            fn as_ref(&self) -> &#uuid {
+                self.#field.as_uuid()
+            }
+        }
+
+        impl #impl_generics ::std::convert::AsRef<[u8]> for #name #ty_generics #where_clause {
+            fn as_ref(&self) -> &[u8] {
+                self.#field.as_bytes()
+            }
+        }
+
+        impl #impl_generics ::std::borrow::Borrow<#uuid> for #name #ty_generics #where_clause {
+            fn borrow(&self) -> &#uuid {
+                self.#field.as_uuid()
+            }
+        }
+    };
+
+    if options.serde {
+        let mut de_generics = input.generics.clone();
+        de_generics.params.insert(0, parse_quote!('de));
+        let (de_impl_generics, _, _) = de_generics.split_for_impl();
+
+        let (serialize, deserialize) = match &options.prefix {
+            Some(prefix) => (
+                quote!(#private::serialize_prefixed(&self.#field, #prefix, serializer)),
+                quote!(#private::deserialize_prefixed(deserializer, #prefix)),
+            ),
+            None => (
+                quote!(#private::serde::Serialize::serialize(&self.#field, serializer)),
+                quote!(<#sg as #private::serde::Deserialize<'de>>::deserialize(deserializer)),
+            ),
+        };
+
+        tokens.extend(quote! {
+            impl #impl_generics #private::serde::Serialize for #name #ty_generics #where_clause {
+                fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+                where
+                    S: #private::serde::Serializer,
+                {
+                    #serialize
+                }
+            }
+
+            impl #de_impl_generics #private::serde::Deserialize<'de> for #name #ty_generics #where_clause {
+                fn deserialize<D>(deserializer: D) -> ::std::result::Result<Self, D::Error>
+                where
+                    D: #private::serde::Deserializer<'de>,
+                {
+                    #deserialize.map(|value| #construct)
+                }
+            }
+        });
+    }
+
+    if options.borsh {
+        tokens.extend(quote! {
+            impl #impl_generics #private::borsh::BorshSerialize for #name #ty_generics #where_clause {
+                fn serialize<W: ::std::io::Write>(&self, writer: &mut W) -> ::std::io::Result<()> {
+                    #private::borsh::BorshSerialize::serialize(&self.#field, writer)
+                }
+            }
+
+            impl #impl_generics #private::borsh::BorshDeserialize for #name #ty_generics #where_clause {
+                fn deserialize_reader<R: ::std::io::Read>(reader: &mut R) -> ::std::io::Result<Self> {
+                    let value = <#sg as #private::borsh::BorshDeserialize>::deserialize_reader(reader)?;
+                    Ok(#construct)
+                }
+            }
+        });
+    }
+
+    if options.bytemuck {
+        if !is_repr_transparent(&input) {
+            return Err(syn::Error::new_spanned(
+                name,
+                "the bytemuck integration requires #[repr(transparent)]",
+            ));
+        }
+
+        tokens.extend(quote! {
+            // SAFETY: The type is a transparent wrapper around a `ShortGuid`.
+            unsafe impl #impl_generics #private::bytemuck::Zeroable for #name #ty_generics #where_clause {}
+
+            // SAFETY: See above; `ShortGuid` is `Pod`.
+            unsafe impl #impl_generics #private::bytemuck::Pod for #name #ty_generics #where_clause {}
+
+            // SAFETY: The type is `#[repr(transparent)]` over its single `ShortGuid` field.
+            unsafe impl #impl_generics #private::bytemuck::TransparentWrapper<#sg> for #name #ty_generics #where_clause {}
+        });
+    }
+
+    Ok(tokens)
+}
@@ -0,0 +1,285 @@
+use crate::ShortGuid;
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+
+/// An index over a set of [`ShortGuid`]s that abbreviates each ID to its shortest unique
+/// prefix, similar to abbreviated commit hashes in git.
+///
+/// ## Example
+///
+/// ```
+/// # use shortguid::{AbbrevIndex, ResolveError, ShortGuid};
+/// let a = ShortGuid::try_parse("yaZG05xhTLe_ze4lIsj2Mw").unwrap();
+/// let b = ShortGuid::try_parse("yaZG1AAAAAAAAAAAAAAAAA").unwrap();
+/// let index = AbbrevIndex::new([a, b]);
+///
+/// assert_eq!(index.unique_prefix_len(&a), Some(5));
+/// assert_eq!(index.abbreviate(&a, 7), Some("yaZG05x"));
+/// assert_eq!(index.resolve("yaZG0").unwrap(), a);
+/// assert_eq!(index.resolve("yaZG").unwrap_err(), ResolveError::Ambiguous(vec![a, b]));
+/// assert_eq!(index.resolve("yaZG2").unwrap_err(), ResolveError::NotFound(vec![b]));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct AbbrevIndex {
+    /// The encoded IDs and their values, sorted by the encoded string.
+    entries: Vec<(String, ShortGuid)>,
+}
+
+impl AbbrevIndex {
+    /// Builds an index over the provided IDs. Duplicates are ignored.
+    pub fn new<I: IntoIterator<Item = ShortGuid>>(ids: I) -> Self {
+        let mut entries: Vec<_> = ids.into_iter().map(|id| (id.to_string(), id)).collect();
+        entries.sort_unstable();
+        entries.dedup();
+        Self { entries }
+    }
+
+    /// Returns the number of IDs in the index.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Tests whether the index is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Tests whether the index contains the ID.
+    pub fn contains(&self, id: &ShortGuid) -> bool {
+        self.position(id).is_some()
+    }
+
+    /// Returns the length of the shortest prefix that identifies the ID within the index,
+    /// or `None` if the ID is not part of the index.
+    pub fn unique_prefix_len(&self, id: &ShortGuid) -> Option<usize> {
+        let position = self.position(id)?;
+        let encoded = &self.entries[position].0;
+
+        let previous = position
+            .checked_sub(1)
+            .map(|i| common_prefix_len(encoded, &self.entries[i].0));
+        let next = self
+            .entries
+            .get(position + 1)
+            .map(|(other, _)| common_prefix_len(encoded, other));
+
+        let shared = previous.unwrap_or(0).max(next.unwrap_or(0));
+        Some((shared + 1).min(encoded.len()))
+    }
+
+    /// Returns the shortest unique prefix of the ID, but at least `min_len` characters,
+    /// or `None` if the ID is not part of the index.
+    pub fn abbreviate(&self, id: &ShortGuid, min_len: usize) -> Option<&str> {
+        let len = self.unique_prefix_len(id)?;
+        let encoded = &self.entries[self.position(id)?].0;
+        Some(&encoded[..len.max(min_len).min(encoded.len())])
+    }
+
+    /// Resolves a prefix to the single ID it identifies.
+    ///
+    /// Returns [`ResolveError::EmptyPrefix`] for an empty prefix,
+    /// [`ResolveError::NotFound`] listing the nearest IDs if no ID starts with the prefix,
+    /// or [`ResolveError::Ambiguous`] listing all candidates if more than one does.
+    pub fn resolve(&self, prefix: &str) -> Result<ShortGuid, ResolveError> {
+        if prefix.is_empty() {
+            return Err(ResolveError::EmptyPrefix);
+        }
+
+        let start = self
+            .entries
+            .partition_point(|(encoded, _)| encoded.as_str() < prefix);
+        let count = self.entries[start..]
+            .iter()
+            .take_while(|(encoded, _)| encoded.starts_with(prefix))
+            .count();
+
+        match &self.entries[start..start + count] {
+            [] => Err(ResolveError::NotFound(self.neighbours(start))),
+            [(_, id)] => Ok(*id),
+            candidates => Err(ResolveError::Ambiguous(
+                candidates.iter().map(|(_, id)| *id).collect(),
+            )),
+        }
+    }
+
+    /// Returns an iterator over the IDs in the order of their encoded strings.
+    pub fn iter(&self) -> impl Iterator<Item = ShortGuid> + '_ {
+        self.entries.iter().map(|(_, id)| *id)
+    }
+
+    /// Returns the IDs sorted directly before and after the insertion point `position`.
+    fn neighbours(&self, position: usize) -> Vec<ShortGuid> {
+        let start = position.saturating_sub(1);
+        let end = (position + 1).min(self.entries.len());
+        self.entries[start..end].iter().map(|(_, id)| *id).collect()
+    }
+
+    /// Returns the position of the ID in the sorted entries.
+    fn position(&self, id: &ShortGuid) -> Option<usize> {
+        let encoded = id.to_string();
+        self.entries
+            .binary_search_by(|(other, _)| other.cmp(&encoded))
+            .ok()
+    }
+}
+
+impl FromIterator<ShortGuid> for AbbrevIndex {
+    fn from_iter<T: IntoIterator<Item = ShortGuid>>(iter: T) -> Self {
+        Self::new(iter)
+    }
+}
+
+/// Returns the number of leading bytes the two strings have in common.
+fn common_prefix_len(a: &str, b: &str) -> usize {
+    a.bytes().zip(b.bytes()).take_while(|(a, b)| a == b).count()
+}
+
+/// An error resolving an abbreviated ID.
+#[derive(Clone, Eq, PartialEq)]
+pub enum ResolveError {
+    /// The provided prefix was empty.
+    EmptyPrefix,
+    /// No ID starts with the provided prefix.
+    /// The contained value lists the IDs sorted directly before and after the prefix.
+    NotFound(Vec<ShortGuid>),
+    /// More than one ID starts with the provided prefix.
+    /// The contained value lists all candidates.
+    Ambiguous(Vec<ShortGuid>),
+}
+
+impl Debug for ResolveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl Display for ResolveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResolveError::EmptyPrefix => write!(f, "The prefix is empty"),
+            ResolveError::NotFound(nearest) if nearest.is_empty() => {
+                write!(f, "No ID matches the prefix")
+            }
+            ResolveError::NotFound(nearest) => {
+                write!(f, "No ID matches the prefix; nearest are ")?;
+                write_ids(f, nearest)
+            }
+            ResolveError::Ambiguous(candidates) => {
+                write!(f, "The prefix is ambiguous; candidates are ")?;
+                write_ids(f, candidates)
+            }
+        }
+    }
+}
+
+/// Writes the IDs as a comma separated list.
+fn write_ids(f: &mut Formatter<'_>, ids: &[ShortGuid]) -> std::fmt::Result {
+    for (i, id) in ids.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{id}")?;
+    }
+    Ok(())
+}
+
+impl Error for ResolveError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id(value: &str) -> ShortGuid {
+        ShortGuid::try_parse(value).unwrap()
+    }
+
+    #[test]
+    fn unique_prefix_len_works() {
+        let a = id("yaZG05xhTLe_ze4lIsj2Mw");
+        let b = id("yaZG1AAAAAAAAAAAAAAAAA");
+        let c = id("AAAAAAAAAAAAAAAAAAAAAA");
+        let index = AbbrevIndex::new([a, b, c, a]);
+
+        assert_eq!(index.len(), 3);
+        assert_eq!(index.unique_prefix_len(&a), Some(5));
+        assert_eq!(index.unique_prefix_len(&b), Some(5));
+        assert_eq!(index.unique_prefix_len(&c), Some(1));
+        assert_eq!(index.unique_prefix_len(&id("BBBBBBBBBBBBBBBBBBBBBA")), None);
+    }
+
+    #[test]
+    fn abbreviate_works() {
+        let a = id("yaZG05xhTLe_ze4lIsj2Mw");
+        let index = AbbrevIndex::new([a]);
+        assert_eq!(index.abbreviate(&a, 0), Some("y"));
+        assert_eq!(index.abbreviate(&a, 7), Some("yaZG05x"));
+        assert_eq!(index.abbreviate(&a, 100), Some("yaZG05xhTLe_ze4lIsj2Mw"));
+    }
+
+    #[test]
+    fn resolve_works() {
+        let a = id("yaZG05xhTLe_ze4lIsj2Mw");
+        let b = id("yaZG1AAAAAAAAAAAAAAAAA");
+        let index: AbbrevIndex = [a, b].into_iter().collect();
+
+        assert_eq!(index.resolve("yaZG05xhTLe_ze4lIsj2Mw").unwrap(), a);
+        assert_eq!(index.resolve("yaZG1").unwrap(), b);
+        assert_eq!(
+            index.resolve("yaZG").unwrap_err(),
+            ResolveError::Ambiguous(vec![a, b])
+        );
+        assert_eq!(index.resolve("").unwrap_err(), ResolveError::EmptyPrefix);
+        assert_eq!(
+            index.resolve("yaZg").unwrap_err(),
+            ResolveError::NotFound(vec![b])
+        );
+        assert_eq!(
+            index.resolve("yaZG05xhTLe_ze4lIsj2Mw_").unwrap_err(),
+            ResolveError::NotFound(vec![a, b])
+        );
+        assert_eq!(
+            index.resolve("A").unwrap_err(),
+            ResolveError::NotFound(vec![a])
+        );
+        assert_eq!(
+            AbbrevIndex::default().resolve("A").unwrap_err(),
+            ResolveError::NotFound(vec![])
+        );
+    }
+
+    #[test]
+    fn every_abbreviation_resolves() {
+        let index: AbbrevIndex = (0..1000).map(|_| ShortGuid::new_random()).collect();
+        for id in index.iter() {
+            let abbreviation = index.abbreviate(&id, 0).unwrap();
+            assert_eq!(index.resolve(abbreviation).unwrap(), id);
+
+            let shorter = &abbreviation[..abbreviation.len() - 1];
+            assert!(index.resolve(shorter).is_err());
+        }
+    }
+
+    #[test]
+    fn error_lists_candidates() {
+        let error = ResolveError::Ambiguous(vec![
+            id("yaZG05xhTLe_ze4lIsj2Mw"),
+            id("yaZG1AAAAAAAAAAAAAAAAA"),
+        ]);
+        assert_eq!(
+            error.to_string(),
+            "The prefix is ambiguous; candidates are yaZG05xhTLe_ze4lIsj2Mw, yaZG1AAAAAAAAAAAAAAAAA"
+        );
+
+        let error = ResolveError::NotFound(vec![id("yaZG05xhTLe_ze4lIsj2Mw")]);
+        assert_eq!(
+            error.to_string(),
+            "No ID matches the prefix; nearest are yaZG05xhTLe_ze4lIsj2Mw"
+        );
+        assert_eq!(
+            ResolveError::NotFound(vec![]).to_string(),
+            "No ID matches the prefix"
+        );
+    }
+}
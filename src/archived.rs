@@ -0,0 +1,131 @@
+use crate::{ArchivedShortGuid, ShortGuid};
+use std::fmt::{Debug, Display, Formatter};
+use uuid::Uuid;
+
+impl ArchivedShortGuid {
+    /// Returns the archived value as a [`ShortGuid`].
+    #[inline]
+    pub const fn to_short_guid(&self) -> ShortGuid {
+        ShortGuid(self.0)
+    }
+
+    /// Returns the archived value as a [`Uuid`].
+    #[inline]
+    pub const fn to_uuid(&self) -> Uuid {
+        Uuid::from_bytes(self.0)
+    }
+
+    /// Returns a slice of 16 octets containing the value.
+    #[inline]
+    pub const fn as_bytes(&self) -> &[u8; 16] {
+        &self.0
+    }
+
+    /// Tests if the archived value is all zeros.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.to_short_guid().is_empty()
+    }
+}
+
+impl Debug for ArchivedShortGuid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&self.to_short_guid(), f)
+    }
+}
+
+impl Display for ArchivedShortGuid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.to_short_guid(), f)
+    }
+}
+
+impl From<&ArchivedShortGuid> for ShortGuid {
+    fn from(value: &ArchivedShortGuid) -> Self {
+        value.to_short_guid()
+    }
+}
+
+impl PartialEq<Uuid> for ArchivedShortGuid {
+    fn eq(&self, other: &Uuid) -> bool {
+        self.as_bytes().eq(other.as_bytes())
+    }
+}
+
+impl AsRef<[u8]> for ArchivedShortGuid {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rkyv::rancor::Error;
+    use std::collections::HashSet;
+    use std::hash::{BuildHasher, RandomState};
+
+    const UUID: &str = "c9a646d3-9c61-4cb7-bfcd-ee2522c8f633";
+
+    #[derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize, Debug, Eq, PartialEq)]
+    struct Table {
+        ids: Vec<ShortGuid>,
+        owner: ShortGuid,
+    }
+
+    fn table() -> Table {
+        Table {
+            ids: vec![
+                ShortGuid::try_parse(UUID).unwrap(),
+                ShortGuid::default(),
+                ShortGuid::from(Uuid::max()),
+            ],
+            owner: ShortGuid::try_parse(UUID).unwrap(),
+        }
+    }
+
+    #[test]
+    fn roundtrip_works() {
+        let bytes = rkyv::to_bytes::<Error>(&table()).unwrap();
+        let archived = rkyv::access::<ArchivedTable, Error>(&bytes).unwrap();
+        assert_eq!(archived.ids.len(), 3);
+        assert_eq!(archived.owner, table().owner);
+        assert_eq!(table().owner, archived.owner);
+        assert_eq!(archived.owner, Uuid::try_parse(UUID).unwrap());
+
+        let deserialized = rkyv::deserialize::<Table, Error>(archived).unwrap();
+        assert_eq!(deserialized, table());
+    }
+
+    #[test]
+    fn archived_traits_match_short_guid() {
+        let bytes = rkyv::to_bytes::<Error>(&table()).unwrap();
+        let archived = rkyv::access::<ArchivedTable, Error>(&bytes).unwrap();
+        let (id, nil, max) = (&archived.ids[0], &archived.ids[1], &archived.ids[2]);
+
+        assert_eq!(id.to_string(), "yaZG05xhTLe_ze4lIsj2Mw");
+        assert_eq!(format!("{id:?}"), format!("{:?}", id.to_short_guid()));
+        assert!(nil.is_empty());
+        assert!(nil < id && id < max);
+        assert!(table().ids[1] < *id);
+        assert_eq!(id, &archived.owner);
+
+        let state = RandomState::new();
+        assert_eq!(state.hash_one(id), state.hash_one(id.to_short_guid()));
+        let unique: HashSet<_> = archived.ids.iter().chain([&archived.owner]).collect();
+        assert_eq!(unique.len(), 3);
+    }
+
+    #[test]
+    fn validation_works() {
+        let id = ShortGuid::try_parse(UUID).unwrap();
+        let bytes = rkyv::to_bytes::<Error>(&id).unwrap();
+        assert_eq!(bytes.as_slice(), id.as_bytes());
+        assert_eq!(
+            rkyv::access::<ArchivedShortGuid, Error>(&bytes).unwrap(),
+            &id
+        );
+        assert!(rkyv::access::<ArchivedShortGuid, Error>(&bytes[..15]).is_err());
+        assert!(rkyv::access::<ArchivedTable, Error>(&bytes).is_err());
+    }
+}
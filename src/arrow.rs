@@ -0,0 +1,455 @@
+//! Apache Arrow column support using `arrow-array`.
+//!
+//! IDs are stored in `FixedSizeBinary(16)` columns, tagged with the canonical `arrow.uuid`
+//! extension type. The bytes of such a column can be viewed as a slice of [`ShortGuid`]s
+//! without copying, and the [`format_short`] and [`parse_short`] kernels convert whole
+//! columns to and from their 22 character string form.
+//!
+//! ```
+//! use shortguid::{arrow, ShortGuid};
+//!
+//! let ids = vec![ShortGuid::new_random(), ShortGuid::new_random()];
+//! let array = arrow::array_from_slice(&ids);
+//! assert_eq!(arrow::as_short_guids(&array).unwrap(), ids.as_slice());
+//!
+//! let strings = arrow::format_short(&array).unwrap();
+//! assert_eq!(strings.value(0), ids[0].to_string());
+//! assert_eq!(arrow::parse_short(&strings).unwrap(), array);
+//! ```
+
+use crate::{bulk, ParseError, ShortGuid};
+use arrow_array::builder::FixedSizeBinaryBuilder;
+use arrow_array::{Array, FixedSizeBinaryArray, StringArray};
+use arrow_buffer::{Buffer, OffsetBuffer};
+use arrow_schema::extension::Uuid;
+use arrow_schema::{ArrowError, DataType, Field};
+
+/// The number of bytes of a [`ShortGuid`].
+const BYTE_WIDTH: i32 = 16;
+
+/// The length of an encoded [`ShortGuid`].
+const SHORT_GUID_LEN: usize = 22;
+
+/// Returns a `FixedSizeBinary(16)` field with the canonical `arrow.uuid` extension type.
+///
+/// ```
+/// use arrow_schema::extension::{ExtensionType, Uuid};
+/// use shortguid::arrow;
+///
+/// let field = arrow::field("id", false);
+/// assert_eq!(field.extension_type_name(), Some(Uuid::NAME));
+/// ```
+pub fn field(name: impl Into<String>, nullable: bool) -> Field {
+    Field::new(name, DataType::FixedSizeBinary(BYTE_WIDTH), nullable).with_extension_type(Uuid)
+}
+
+/// Copies the IDs into a new array.
+pub fn array_from_slice(ids: &[ShortGuid]) -> FixedSizeBinaryArray {
+    let bytes = Buffer::from_slice_ref(ids_as_bytes(ids));
+    FixedSizeBinaryArray::new(BYTE_WIDTH, bytes, None)
+}
+
+/// Converts the IDs into a new array, reusing the allocation of the vector.
+pub fn array_from_vec(ids: Vec<ShortGuid>) -> FixedSizeBinaryArray {
+    FixedSizeBinaryArray::new(BYTE_WIDTH, vec_into_buffer(ids), None)
+}
+
+/// Converts the IDs into a buffer, reusing the allocation of the vector.
+fn vec_into_buffer(ids: Vec<ShortGuid>) -> Buffer {
+    let mut ids = std::mem::ManuallyDrop::new(ids);
+    let (ptr, len, capacity) = (ids.as_mut_ptr(), ids.len(), ids.capacity());
+
+    // SAFETY: `ShortGuid` is a transparent wrapper around `[u8; 16]` with an alignment of one,
+    // so the allocation has the same layout as one of `16 * capacity` bytes, of which the
+    // first `16 * len` are initialized. Ownership is transferred from the forgotten vector.
+    let bytes = unsafe { Vec::from_raw_parts(ptr.cast::<u8>(), len * 16, capacity * 16) };
+    Buffer::from_vec(bytes)
+}
+
+/// Views the values of the array as a slice of IDs without copying.
+///
+/// The slice has one entry per slot of the array; the entries of null slots are unspecified.
+/// Returns an error if the array is not of type `FixedSizeBinary(16)`.
+pub fn as_short_guids(array: &FixedSizeBinaryArray) -> Result<&[ShortGuid], ArrowError> {
+    check_byte_width(array)?;
+    let bytes = array.value_data();
+    debug_assert_eq!(bytes.len(), array.len() * 16);
+
+    // SAFETY: `ShortGuid` is a transparent wrapper around `[u8; 16]` with an alignment of one,
+    // and the values of the array are exactly `16 * len` bytes.
+    Ok(unsafe { std::slice::from_raw_parts(bytes.as_ptr().cast::<ShortGuid>(), array.len()) })
+}
+
+/// Encodes each ID of the array into its 22 character form. Null slots remain null.
+///
+/// Returns an error if the array is not of type `FixedSizeBinary(16)`, or if the
+/// encoded values would exceed the 2 GiB limit of a `StringArray`.
+pub fn format_short(array: &FixedSizeBinaryArray) -> Result<StringArray, ArrowError> {
+    let ids = as_short_guids(array)?;
+    let len = ids.len();
+    let total = len
+        .checked_mul(SHORT_GUID_LEN)
+        .filter(|&total| i32::try_from(total).is_ok())
+        .ok_or_else(|| {
+            ArrowError::ComputeError(format!("{len} encoded IDs exceed the StringArray capacity"))
+        })?;
+
+    let mut values = vec![0u8; total];
+    bulk::encode_many(ids, &mut values);
+
+    let offsets = OffsetBuffer::from_lengths(std::iter::repeat(SHORT_GUID_LEN).take(len));
+    let nulls = array.nulls().cloned();
+
+    // SAFETY: The values are ASCII characters of the Base64 alphabet, and the offsets
+    // were derived from the length of the values.
+    Ok(unsafe { StringArray::new_unchecked(offsets, Buffer::from_vec(values), nulls) })
+}
+
+/// Parses each 22 character [`ShortGuid`] of the array. Null slots remain null.
+///
+/// If every slot holds exactly 22 bytes, the values are decoded straight from the
+/// value buffer; otherwise, they are gathered into a contiguous buffer first.
+///
+/// Returns an error naming the first slot that does not hold a valid [`ShortGuid`].
+pub fn parse_short(array: &StringArray) -> Result<FixedSizeBinaryArray, ArrowError> {
+    let offsets = array.value_offsets();
+    let contiguous = offsets
+        .windows(2)
+        .all(|window| window[1] - window[0] == SHORT_GUID_LEN as i32);
+
+    let gathered;
+    let mut invalid_length = None;
+    let input = if contiguous {
+        let (start, end) = (offsets[0] as usize, offsets[array.len()] as usize);
+        &array.value_data()[start..end]
+    } else {
+        (gathered, invalid_length) = gather_short(array);
+        gathered.as_slice()
+    };
+
+    let mut ids = vec![ShortGuid::default(); array.len()];
+    let invalid_value = bulk::decode_many(input, &mut ids)
+        .err()
+        .and_then(|errors| errors.into_iter().find(|(i, _)| array.is_valid(*i)));
+    let first_error = [invalid_length, invalid_value]
+        .into_iter()
+        .flatten()
+        .min_by_key(|(i, _)| *i);
+    if let Some((i, e)) = first_error {
+        let value = array.value(i);
+        return Err(ArrowError::ParseError(format!(
+            "Invalid ShortGuid {value:?} at index {i}: {e}"
+        )));
+    }
+
+    for (i, id) in ids.iter_mut().enumerate() {
+        if array.is_null(i) {
+            *id = ShortGuid::default();
+        }
+    }
+
+    let nulls = array.nulls().cloned();
+    FixedSizeBinaryArray::try_new(BYTE_WIDTH, vec_into_buffer(ids), nulls)
+}
+
+/// Copies the values of the array into consecutive 22 byte strings, using the nil ID
+/// for null slots and values of another length. Returns the first slot with an invalid length.
+fn gather_short(array: &StringArray) -> (Vec<u8>, Option<(usize, ParseError)>) {
+    const NIL: &[u8; SHORT_GUID_LEN] = b"AAAAAAAAAAAAAAAAAAAAAA";
+
+    let mut gathered = Vec::with_capacity(array.len() * SHORT_GUID_LEN);
+    let mut invalid_length = None;
+    for i in 0..array.len() {
+        if array.is_null(i) {
+            gathered.extend_from_slice(NIL);
+            continue;
+        }
+
+        let value = array.value(i).as_bytes();
+        if value.len() == SHORT_GUID_LEN {
+            gathered.extend_from_slice(value);
+        } else {
+            gathered.extend_from_slice(NIL);
+            invalid_length.get_or_insert((i, ParseError::InvalidLength(value.len())));
+        }
+    }
+    (gathered, invalid_length)
+}
+
+/// A builder for `FixedSizeBinary(16)` arrays of [`ShortGuid`]s.
+///
+/// ```
+/// use arrow_array::Array;
+/// use shortguid::arrow::ShortGuidBuilder;
+/// use shortguid::ShortGuid;
+///
+/// let mut builder = ShortGuidBuilder::with_capacity(2);
+/// builder.append_value(ShortGuid::new_random());
+/// builder.append_null();
+/// let array = builder.finish();
+/// assert_eq!(array.len(), 2);
+/// assert!(array.is_null(1));
+/// ```
+#[derive(Debug)]
+pub struct ShortGuidBuilder {
+    inner: FixedSizeBinaryBuilder,
+}
+
+impl ShortGuidBuilder {
+    /// Creates a new, empty builder.
+    pub fn new() -> Self {
+        Self::with_capacity(1024)
+    }
+
+    /// Creates a new, empty builder with space for `capacity` IDs.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            inner: FixedSizeBinaryBuilder::with_capacity(capacity, BYTE_WIDTH),
+        }
+    }
+
+    /// Appends an ID.
+    pub fn append_value(&mut self, id: ShortGuid) {
+        self.inner
+            .append_value(id.as_bytes())
+            .expect("16 bytes match the byte width");
+    }
+
+    /// Appends a null slot.
+    pub fn append_null(&mut self) {
+        self.inner.append_null();
+    }
+
+    /// Appends an ID, or a null slot if the value is `None`.
+    pub fn append_option(&mut self, id: Option<ShortGuid>) {
+        match id {
+            Some(id) => self.append_value(id),
+            None => self.append_null(),
+        }
+    }
+
+    /// Returns the number of slots appended so far.
+    pub fn len(&self) -> usize {
+        self.inner.values_slice().len() / 16
+    }
+
+    /// Tests whether no slots were appended yet.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Builds the array and resets this builder.
+    pub fn finish(&mut self) -> FixedSizeBinaryArray {
+        self.inner.finish()
+    }
+}
+
+impl Default for ShortGuidBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Extend<ShortGuid> for ShortGuidBuilder {
+    fn extend<T: IntoIterator<Item = ShortGuid>>(&mut self, iter: T) {
+        iter.into_iter().for_each(|id| self.append_value(id));
+    }
+}
+
+impl Extend<Option<ShortGuid>> for ShortGuidBuilder {
+    fn extend<T: IntoIterator<Item = Option<ShortGuid>>>(&mut self, iter: T) {
+        iter.into_iter().for_each(|id| self.append_option(id));
+    }
+}
+
+/// Returns an error unless the array is of type `FixedSizeBinary(16)`.
+fn check_byte_width(array: &FixedSizeBinaryArray) -> Result<(), ArrowError> {
+    if array.value_length() != BYTE_WIDTH {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "Expected FixedSizeBinary(16), found {}",
+            array.data_type()
+        )));
+    }
+    Ok(())
+}
+
+/// Reinterprets the IDs as their contiguous bytes.
+fn ids_as_bytes(ids: &[ShortGuid]) -> &[u8] {
+    // SAFETY: `ShortGuid` is a transparent wrapper around `[u8; 16]` without padding.
+    unsafe { std::slice::from_raw_parts(ids.as_ptr().cast::<u8>(), ids.len() * 16) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::{ArrayRef, RecordBatch};
+    use arrow_schema::extension::ExtensionType;
+    use arrow_schema::Schema;
+    use std::sync::Arc;
+
+    fn ids() -> Vec<ShortGuid> {
+        [
+            "yaZG05xhTLe_ze4lIsj2Mw",
+            "AAAAAAAAAAAAAAAAAAAAAA",
+            "ELina62d0RGAtADAT9QwyA",
+        ]
+        .iter()
+        .map(|id| ShortGuid::try_parse(id).unwrap())
+        .collect()
+    }
+
+    #[test]
+    fn conversion_works() {
+        let ids = ids();
+        let array = array_from_slice(&ids);
+        assert_eq!(array.len(), 3);
+        assert_eq!(array.value(0), ids[0].as_bytes());
+        assert_eq!(as_short_guids(&array).unwrap(), ids.as_slice());
+
+        let array = array_from_vec(ids.clone());
+        assert_eq!(as_short_guids(&array).unwrap(), ids.as_slice());
+
+        let sliced = array.slice(1, 2);
+        assert_eq!(as_short_guids(&sliced).unwrap(), &ids[1..]);
+
+        let other = FixedSizeBinaryArray::new(4, Buffer::from_vec(vec![0u8; 8]), None);
+        assert!(as_short_guids(&other).is_err());
+    }
+
+    #[test]
+    fn array_from_vec_reuses_allocation() {
+        let ids = ids();
+        let ptr = ids.as_ptr().cast::<u8>();
+        let array = array_from_vec(ids);
+        assert_eq!(array.value_data().as_ptr(), ptr);
+        assert_eq!(as_short_guids(&array).unwrap().as_ptr().cast::<u8>(), ptr);
+    }
+
+    #[test]
+    fn builder_works() {
+        let ids = ids();
+        let mut builder = ShortGuidBuilder::new();
+        builder.extend([Some(ids[0]), None]);
+        builder.extend([ids[2]]);
+        assert_eq!(builder.len(), 3);
+
+        let array = builder.finish();
+        assert!(builder.is_empty());
+        assert_eq!(array.null_count(), 1);
+        assert_eq!(array.value(0), ids[0].as_bytes());
+        assert_eq!(array.value(2), ids[2].as_bytes());
+    }
+
+    #[test]
+    fn format_and_parse_work() {
+        let ids = ids();
+        let mut builder = ShortGuidBuilder::new();
+        builder.extend([Some(ids[0]), None, Some(ids[2])]);
+        let array = builder.finish();
+
+        let strings = format_short(&array).unwrap();
+        assert_eq!(
+            strings.iter().collect::<Vec<_>>(),
+            [
+                Some("yaZG05xhTLe_ze4lIsj2Mw"),
+                None,
+                Some("ELina62d0RGAtADAT9QwyA")
+            ]
+        );
+        assert_eq!(parse_short(&strings).unwrap(), array);
+
+        let sliced = format_short(&array.slice(2, 1)).unwrap();
+        assert_eq!(sliced.value(0), "ELina62d0RGAtADAT9QwyA");
+    }
+
+    #[test]
+    fn parse_contiguous_values_works() {
+        // Every slot holds 22 bytes, including the null slot.
+        let ids = ids();
+        let strings = StringArray::new(
+            OffsetBuffer::from_lengths([SHORT_GUID_LEN; 3]),
+            Buffer::from_vec(
+                "yaZG05xhTLe_ze4lIsj2Mw!!!!!!!!!!!!!!!!!!!!!!ELina62d0RGAtADAT9QwyA"
+                    .as_bytes()
+                    .to_vec(),
+            ),
+            Some(vec![true, false, true].into()),
+        );
+        let array = parse_short(&strings).unwrap();
+        assert_eq!(array.null_count(), 1);
+        assert_eq!(as_short_guids(&array).unwrap(), ids.as_slice());
+
+        let sliced = parse_short(&strings.slice(2, 1)).unwrap();
+        assert_eq!(as_short_guids(&sliced).unwrap(), &ids[2..]);
+    }
+
+    #[test]
+    fn parse_invalid_fails() {
+        let strings = StringArray::from(vec![
+            Some("yaZG05xhTLe_ze4lIsj2Mw"),
+            None,
+            Some("c9a646d3-9c61-4cb7-bfcd-ee2522c8f633"),
+        ]);
+        let error = parse_short(&strings).unwrap_err();
+        assert!(
+            error.to_string().contains("at index 2"),
+            "unexpected error {error}"
+        );
+
+        let strings =
+            StringArray::from(vec!["yaZG05xhTLe_ze4lIsj2Mw", "Nothing to see here...", ""]);
+        let error = parse_short(&strings).unwrap_err();
+        assert!(
+            error.to_string().contains("at index 1"),
+            "unexpected error {error}"
+        );
+
+        let strings = StringArray::from(vec!["yaZG05xhTLe_ze4lIsj2Mw", ""]);
+        assert_eq!(
+            parse_short(&strings).unwrap_err().to_string(),
+            "Parser error: Invalid ShortGuid \"\" at index 1: \
+             Invalid ID length; expected 22 characters, but got 0"
+        );
+    }
+
+    #[test]
+    fn parquet_roundtrip_works() {
+        use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+        use parquet::arrow::ArrowWriter;
+
+        let ids = ids();
+        let mut builder = ShortGuidBuilder::new();
+        builder.extend([Some(ids[0]), None, Some(ids[2])]);
+        let parents: ArrayRef = Arc::new(builder.finish());
+        let schema = Arc::new(Schema::new(vec![field("id", false), field("parent", true)]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(array_from_vec(ids.clone())), parents],
+        )
+        .unwrap();
+
+        let mut buffer = Vec::new();
+        let mut writer = ArrowWriter::try_new(&mut buffer, schema.clone(), None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+
+        let reader = ParquetRecordBatchReaderBuilder::try_new(bytes::Bytes::from(buffer))
+            .unwrap()
+            .build()
+            .unwrap();
+        let batches: Vec<_> = reader.map(Result::unwrap).collect();
+        assert_eq!(batches, [batch]);
+
+        let read = &batches[0];
+        assert_eq!(
+            read.schema().field(0).extension_type_name(),
+            Some(Uuid::NAME)
+        );
+        let id = read
+            .column(0)
+            .as_any()
+            .downcast_ref::<FixedSizeBinaryArray>()
+            .unwrap();
+        assert_eq!(as_short_guids(id).unwrap(), ids.as_slice());
+    }
+}
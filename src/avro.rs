@@ -0,0 +1,252 @@
+//! Apache Avro support for [`ShortGuid`].
+//!
+//! A [`ShortGuid`] converts into the `uuid` logical type [`Value`], which the writer encodes
+//! as a `string` or as 16 raw bytes depending on whether the field is declared on a `string`
+//! or a `fixed(16)`. When deriving [`AvroSchema`](apache_avro::AvroSchema), a [`ShortGuid`]
+//! describes itself using the `uuid` logical type on a `string`. For plain `fixed(16)`
+//! fields, use [`ShortGuid::to_avro_fixed`]. Reading accepts all of these representations
+//! as well as strings holding a [`ShortGuid`].
+
+use crate::{ParseError, ShortGuid};
+use apache_avro::schema::{Name, NamespaceRef, UuidSchema};
+use apache_avro::types::Value;
+use apache_avro::{AvroSchemaComponent, Schema};
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+
+/// Describes a [`ShortGuid`] using the `uuid` logical type on a `string`.
+///
+/// Unlike the implementation for [`Uuid`](uuid::Uuid), this does not declare a named
+/// `fixed(16)` schema; values still convert into `fixed(16)` fields with the `uuid`
+/// logical type when the schema is given explicitly.
+impl AvroSchemaComponent for ShortGuid {
+    fn get_schema_in_ctxt(
+        _named_schemas: &mut HashSet<Name>,
+        _enclosing_namespace: NamespaceRef,
+    ) -> Schema {
+        Schema::Uuid(UuidSchema::String)
+    }
+}
+
+impl ShortGuid {
+    /// Converts this [`ShortGuid`] into an Avro `fixed` value of 16 bytes.
+    ///
+    /// Use this for plain `fixed(16)` fields; [`Value::from`] produces the `uuid` logical
+    /// type value used for `string` and `fixed(16)` fields with the `uuid` logical type.
+    ///
+    /// ```
+    /// # use shortguid::ShortGuid;
+    /// use apache_avro::types::Value;
+    ///
+    /// let id = ShortGuid::try_parse("c9a646d3-9c61-4cb7-bfcd-ee2522c8f633").unwrap();
+    /// assert_eq!(id.to_avro_fixed(), Value::Fixed(16, id.as_bytes().to_vec()));
+    /// assert_eq!(ShortGuid::try_from(id.to_avro_fixed()).unwrap(), id);
+    /// ```
+    pub fn to_avro_fixed(&self) -> Value {
+        Value::Fixed(16, self.as_bytes().to_vec())
+    }
+}
+
+impl From<ShortGuid> for Value {
+    fn from(value: ShortGuid) -> Self {
+        Value::Uuid(value.into())
+    }
+}
+
+/// Reads a [`ShortGuid`] from a `uuid` logical type value, a `string` holding a
+/// [`ShortGuid`] or hyphenated UUID, or a `fixed` or `bytes` value of 16 bytes.
+/// Union values are unwrapped. Empty strings are rejected rather than read as the nil ID.
+impl TryFrom<&Value> for ShortGuid {
+    type Error = ParseError;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Uuid(uuid) => Ok(Self::from(*uuid)),
+            Value::String(value) => Self::try_parse_non_empty(value),
+            Value::Fixed(_, bytes) | Value::Bytes(bytes) => <[u8; 16]>::try_from(bytes.as_slice())
+                .map(Self::from_bytes)
+                .map_err(|_| AvroError::InvalidLength(bytes.len()).into()),
+            Value::Union(_, value) => Self::try_from(value.as_ref()),
+            _ => Err(AvroError::InvalidType.into()),
+        }
+    }
+}
+
+impl TryFrom<Value> for ShortGuid {
+    type Error = ParseError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        Self::try_from(&value)
+    }
+}
+
+/// An Avro specific conversion error.
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum AvroError {
+    /// The value was neither a `uuid`, `string`, `fixed` nor `bytes` value.
+    InvalidType,
+    /// The `fixed` or `bytes` value had an invalid length.
+    /// The contained value is the actual number of bytes.
+    InvalidLength(usize),
+}
+
+impl From<AvroError> for ParseError {
+    fn from(value: AvroError) -> Self {
+        Self::InvalidAvro(value)
+    }
+}
+
+impl Debug for AvroError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl Display for AvroError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AvroError::InvalidType => write!(
+                f,
+                "invalid type; expected a uuid, string, fixed or bytes value"
+            ),
+            AvroError::InvalidLength(len) => {
+                write!(f, "invalid length; expected 16 bytes, but got {len}")
+            }
+        }
+    }
+}
+
+impl Error for AvroError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use apache_avro::{AvroSchema, Reader, Writer};
+
+    const UUID: &str = "c9a646d3-9c61-4cb7-bfcd-ee2522c8f633";
+
+    const SCHEMA: &str = r#"
+        {
+            "type": "record",
+            "name": "Order",
+            "fields": [
+                {"name": "id", "type": {"type": "string", "logicalType": "uuid"}},
+                {"name": "raw", "type": {"type": "fixed", "name": "Raw", "size": 16}},
+                {"name": "parent", "type": ["null", {"type": "string", "logicalType": "uuid"}]}
+            ]
+        }
+    "#;
+
+    const FIXED_SCHEMA: &str = r#"
+        {
+            "type": "record",
+            "name": "Event",
+            "fields": [
+                {"name": "id", "type": {"type": "fixed", "name": "Id", "size": 16, "logicalType": "uuid"}}
+            ]
+        }
+    "#;
+
+    #[test]
+    fn schema_works() {
+        assert_eq!(ShortGuid::get_schema(), Schema::Uuid(UuidSchema::String));
+        assert_eq!(
+            ShortGuid::get_schema(),
+            Schema::parse_str(r#"{"type": "string", "logicalType": "uuid"}"#).unwrap()
+        );
+    }
+
+    #[test]
+    fn roundtrip_works() {
+        let schema = Schema::parse_str(SCHEMA).unwrap();
+        let id = ShortGuid::try_parse(UUID).unwrap();
+        let parent = ShortGuid::try_parse("yaZG1AAAAAAAAAAAAAAAAA").unwrap();
+
+        let mut writer = Writer::new(&schema, Vec::new()).unwrap();
+        writer
+            .append_value(Value::Record(vec![
+                ("id".to_string(), id.into()),
+                ("raw".to_string(), id.to_avro_fixed()),
+                (
+                    "parent".to_string(),
+                    Value::Union(1, Box::new(parent.into())),
+                ),
+            ]))
+            .unwrap();
+        let bytes = writer.into_inner().unwrap();
+        assert!(bytes.windows(16).any(|window| window == id.as_bytes()));
+
+        let records: Vec<_> = Reader::new(bytes.as_slice())
+            .unwrap()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(records.len(), 1);
+
+        let Value::Record(fields) = &records[0] else {
+            panic!("unexpected value {:?}", records[0]);
+        };
+        assert_eq!(ShortGuid::try_from(&fields[0].1).unwrap(), id);
+        assert_eq!(ShortGuid::try_from(&fields[1].1).unwrap(), id);
+        assert_eq!(ShortGuid::try_from(&fields[2].1).unwrap(), parent);
+    }
+
+    #[test]
+    fn fixed_uuid_roundtrip_works() {
+        let schema = Schema::parse_str(FIXED_SCHEMA).unwrap();
+        let id = ShortGuid::try_parse(UUID).unwrap();
+
+        let mut writer = Writer::new(&schema, Vec::new()).unwrap();
+        writer
+            .append_value(Value::Record(vec![("id".to_string(), id.into())]))
+            .unwrap();
+        let bytes = writer.into_inner().unwrap();
+        assert!(bytes.windows(16).any(|window| window == id.as_bytes()));
+        assert!(!bytes
+            .windows(UUID.len())
+            .any(|window| window == UUID.as_bytes()));
+
+        let records: Vec<_> = Reader::new(bytes.as_slice())
+            .unwrap()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(records.len(), 1);
+
+        let Value::Record(fields) = &records[0] else {
+            panic!("unexpected value {:?}", records[0]);
+        };
+        assert_eq!(fields[0].1, Value::Uuid(id.into()));
+        assert_eq!(ShortGuid::try_from(&fields[0].1).unwrap(), id);
+    }
+
+    #[test]
+    fn conversion_works() {
+        let id = ShortGuid::try_parse(UUID).unwrap();
+        for value in [
+            Value::Uuid(id.into()),
+            Value::String(UUID.to_string()),
+            Value::String(id.to_string()),
+            Value::Fixed(16, id.as_bytes().to_vec()),
+            Value::Bytes(id.as_bytes().to_vec()),
+        ] {
+            assert_eq!(ShortGuid::try_from(&value).unwrap(), id, "{value:?}");
+        }
+    }
+
+    #[test]
+    fn invalid_values_fail() {
+        assert_eq!(
+            ShortGuid::try_from(Value::Long(42)).unwrap_err(),
+            ParseError::InvalidAvro(AvroError::InvalidType)
+        );
+        assert_eq!(
+            ShortGuid::try_from(Value::Fixed(4, vec![0; 4])).unwrap_err(),
+            ParseError::InvalidAvro(AvroError::InvalidLength(4))
+        );
+        assert!(ShortGuid::try_from(Value::String("not an id".to_string())).is_err());
+        assert_eq!(
+            ShortGuid::try_from(Value::String(String::new())).unwrap_err(),
+            ParseError::InvalidLength(0)
+        );
+    }
+}
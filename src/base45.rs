@@ -0,0 +1,193 @@
+use crate::{ParseError, ShortGuid};
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+
+/// The Base45 alphabet as defined in RFC 9285, which is a subset of the QR code
+/// alphanumeric mode character set.
+const ALPHABET: &[u8; 45] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
+
+/// The length of a Base45 encoded [`ShortGuid`].
+const ENCODED_LEN: usize = 24;
+
+impl ShortGuid {
+    /// Encodes this [`ShortGuid`] as a 24 character [RFC 9285](https://www.rfc-editor.org/rfc/rfc9285)
+    /// Base45 string.
+    ///
+    /// The Base45 alphabet fits the QR code alphanumeric mode, which results in smaller
+    /// QR codes than the URL-safe Base64 representation, which requires byte mode.
+    ///
+    /// ```
+    /// # use shortguid::ShortGuid;
+    /// let id = ShortGuid::try_parse("c9a646d3-9c61-4cb7-bfcd-ee2522c8f633").unwrap();
+    /// let encoded = id.encode_base45();
+    /// assert_eq!(encoded.len(), 24);
+    /// assert_eq!(ShortGuid::parse_base45(encoded).unwrap(), id);
+    /// ```
+    pub fn encode_base45(&self) -> String {
+        let encoded = encode(self.as_bytes());
+        debug_assert_eq!(encoded.len(), ENCODED_LEN);
+        encoded
+    }
+
+    /// Parses a 24 character Base45 string as produced by [`ShortGuid::encode_base45`].
+    pub fn parse_base45<S: AsRef<str>>(value: S) -> Result<Self, ParseError> {
+        let value = value.as_ref();
+        if value.len() != ENCODED_LEN {
+            return Err(Base45Error::InvalidLength(value.len()).into());
+        }
+
+        let bytes = decode(value)?;
+        Self::from_slice(bytes)
+    }
+}
+
+/// Encodes arbitrary bytes as Base45.
+fn encode(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity((bytes.len() + 1) / 2 * 3);
+    for chunk in bytes.chunks(2) {
+        let (mut value, digits) = match *chunk {
+            [a, b] => ((a as usize) << 8 | b as usize, 3),
+            [a] => (a as usize, 2),
+            _ => unreachable!("chunks have one or two elements"),
+        };
+
+        for _ in 0..digits {
+            encoded.push(ALPHABET[value % 45] as char);
+            value /= 45;
+        }
+    }
+    encoded
+}
+
+/// Decodes a Base45 string to its bytes.
+fn decode(value: &str) -> Result<Vec<u8>, Base45Error> {
+    if value.len() % 3 == 1 {
+        return Err(Base45Error::InvalidLength(value.len()));
+    }
+
+    let digits = value
+        .chars()
+        .map(|c| {
+            ALPHABET
+                .iter()
+                .position(|&a| a as char == c)
+                .ok_or(Base45Error::InvalidCharacter(c))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut bytes = Vec::with_capacity(digits.len() / 3 * 2 + 1);
+    for chunk in digits.chunks(3) {
+        let value = chunk.iter().rev().fold(0, |acc, &digit| acc * 45 + digit);
+        match chunk.len() {
+            3 if value <= 0xffff => bytes.extend_from_slice(&(value as u16).to_be_bytes()),
+            2 if value <= 0xff => bytes.push(value as u8),
+            _ => return Err(Base45Error::Overflow),
+        }
+    }
+    Ok(bytes)
+}
+
+/// A Base45 specific parsing error.
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum Base45Error {
+    /// The input had an invalid length.
+    /// The contained value is the actual size.
+    InvalidLength(usize),
+    /// The input contained a character outside the Base45 alphabet.
+    InvalidCharacter(char),
+    /// A group of characters encoded a value larger than its bytes can hold.
+    Overflow,
+}
+
+impl From<Base45Error> for ParseError {
+    fn from(value: Base45Error) -> Self {
+        Self::InvalidBase45(value)
+    }
+}
+
+impl Debug for Base45Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl Display for Base45Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Base45Error::InvalidLength(len) => write!(
+                f,
+                "invalid length; expected {ENCODED_LEN} characters, but got {len}"
+            ),
+            Base45Error::InvalidCharacter(c) => write!(f, "invalid character {c:?}"),
+            Base45Error::Overflow => write!(f, "character group exceeds its value range"),
+        }
+    }
+}
+
+impl Error for Base45Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The examples given in RFC 9285.
+    const RFC_VECTORS: &[(&[u8], &str)] = &[
+        (b"AB", "BB8"),
+        (b"Hello!!", "%69 VD92EX0"),
+        (b"base-45", "UJCLQE7W581"),
+        (b"ietf!", "QED8WEX0"),
+    ];
+
+    #[test]
+    fn rfc_vectors_roundtrip() {
+        for &(bytes, encoded) in RFC_VECTORS {
+            assert_eq!(encode(bytes), encoded);
+            assert_eq!(decode(encoded).unwrap(), bytes);
+        }
+    }
+
+    #[test]
+    fn roundtrip_works() {
+        for value in [
+            "00000000-0000-0000-0000-000000000000",
+            "ffffffff-ffff-ffff-ffff-ffffffffffff",
+            "c9a646d3-9c61-4cb7-bfcd-ee2522c8f633",
+        ] {
+            let id = ShortGuid::try_parse(value).unwrap();
+            assert_eq!(ShortGuid::parse_base45(id.encode_base45()).unwrap(), id);
+        }
+    }
+
+    #[test]
+    fn parse_invalid_input_fails() {
+        assert_eq!(
+            ShortGuid::parse_base45("BB8").unwrap_err(),
+            ParseError::InvalidBase45(Base45Error::InvalidLength(3))
+        );
+        assert_eq!(
+            ShortGuid::parse_base45("000000000000000000000aaa").unwrap_err(),
+            ParseError::InvalidBase45(Base45Error::InvalidCharacter('a'))
+        );
+        assert_eq!(
+            ShortGuid::parse_base45("000000000000000000000:::").unwrap_err(),
+            ParseError::InvalidBase45(Base45Error::Overflow)
+        );
+    }
+
+    #[test]
+    fn qr_payload_fits_smaller_versions() {
+        use qrcode::{EcLevel, QrCode, Version};
+
+        let id = ShortGuid::try_parse("c9a646d3-9c61-4cb7-bfcd-ee2522c8f633").unwrap();
+
+        // Base45 uses the alphanumeric mode and fits into a version 1 code ...
+        let base45 = id.encode_base45();
+        assert!(QrCode::with_version(&base45, Version::Normal(1), EcLevel::L).is_ok());
+        assert!(QrCode::with_version(&base45, Version::Normal(2), EcLevel::Q).is_ok());
+
+        // ... whereas Base64 requires byte mode and a larger code.
+        let base64 = id.to_string();
+        assert!(QrCode::with_version(&base64, Version::Normal(1), EcLevel::L).is_err());
+        assert!(QrCode::with_version(&base64, Version::Normal(2), EcLevel::Q).is_err());
+    }
+}
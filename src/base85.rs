@@ -0,0 +1,253 @@
+use crate::{ParseError, ShortGuid};
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+
+/// The Z85 alphabet as defined in ZeroMQ RFC 32.
+const Z85_ALPHABET: &[u8; 85] =
+    b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#";
+
+/// The first character of the Ascii85 alphabet; the alphabet spans `!` to `u`.
+const ASCII85_OFFSET: u8 = b'!';
+
+/// The length of a Z85 encoded [`ShortGuid`].
+const Z85_ENCODED_LEN: usize = 20;
+
+impl ShortGuid {
+    /// Encodes this [`ShortGuid`] as a 20 character [Z85](https://rfc.zeromq.org/spec/32/) string.
+    ///
+    /// ```
+    /// # use shortguid::ShortGuid;
+    /// let id = ShortGuid::try_parse("c9a646d3-9c61-4cb7-bfcd-ee2522c8f633").unwrap();
+    /// let encoded = id.encode_z85();
+    /// assert_eq!(encoded.len(), 20);
+    /// assert_eq!(ShortGuid::parse_z85(encoded).unwrap(), id);
+    /// ```
+    pub fn encode_z85(&self) -> String {
+        let mut encoded = String::with_capacity(Z85_ENCODED_LEN);
+        for chunk in self.as_bytes().chunks_exact(4) {
+            let value = u32::from_be_bytes(chunk.try_into().expect("chunk has 4 bytes"));
+            encoded.extend(to_digits(value).map(|d| Z85_ALPHABET[d as usize] as char));
+        }
+        encoded
+    }
+
+    /// Parses a 20 character Z85 string as produced by [`ShortGuid::encode_z85`].
+    pub fn parse_z85<S: AsRef<str>>(value: S) -> Result<Self, ParseError> {
+        let value = value.as_ref();
+        if value.len() != Z85_ENCODED_LEN {
+            return Err(Base85Error::InvalidLength(value.len()).into());
+        }
+
+        let mut bytes = [0u8; 16];
+        for (group, chunk) in bytes.chunks_exact_mut(4).zip(value.as_bytes().chunks(5)) {
+            let digits = chunk.iter().map(|&c| {
+                Z85_ALPHABET
+                    .iter()
+                    .position(|&a| a == c)
+                    .map(|d| d as u8)
+                    .ok_or(Base85Error::InvalidCharacter(c as char))
+            });
+            group.copy_from_slice(&from_digits(digits)?.to_be_bytes());
+        }
+        Ok(Self::from_bytes(bytes))
+    }
+
+    /// Encodes this [`ShortGuid`] as an Ascii85 string, without the `<~` and `~>` delimiters.
+    ///
+    /// Groups of four zero bytes are abbreviated as `z`, so the result has between
+    /// four and 20 characters.
+    ///
+    /// ```
+    /// # use shortguid::ShortGuid;
+    /// let id = ShortGuid::try_parse("c9a646d3-9c61-4cb7-bfcd-ee2522c8f633").unwrap();
+    /// assert_eq!(ShortGuid::parse_ascii85(id.encode_ascii85()).unwrap(), id);
+    /// assert_eq!(ShortGuid::default().encode_ascii85(), "zzzz");
+    /// ```
+    pub fn encode_ascii85(&self) -> String {
+        let mut encoded = String::with_capacity(Z85_ENCODED_LEN);
+        for chunk in self.as_bytes().chunks_exact(4) {
+            match u32::from_be_bytes(chunk.try_into().expect("chunk has 4 bytes")) {
+                0 => encoded.push('z'),
+                value => encoded.extend(to_digits(value).map(|d| (d + ASCII85_OFFSET) as char)),
+            }
+        }
+        encoded
+    }
+
+    /// Parses an Ascii85 string as produced by [`ShortGuid::encode_ascii85`].
+    ///
+    /// The `<~` and `~>` delimiters are optional, and groups of four zero bytes may
+    /// either be spelled out or abbreviated as `z`.
+    pub fn parse_ascii85<S: AsRef<str>>(value: S) -> Result<Self, ParseError> {
+        let value = value.as_ref();
+        let value = value
+            .strip_prefix("<~")
+            .and_then(|v| v.strip_suffix("~>"))
+            .unwrap_or(value);
+
+        let mut bytes = [0u8; 16];
+        let mut groups = bytes.chunks_exact_mut(4);
+        let mut input = value.as_bytes();
+        while !input.is_empty() {
+            let group = groups
+                .next()
+                .ok_or(Base85Error::InvalidLength(value.len()))?;
+
+            if input[0] == b'z' {
+                input = &input[1..];
+                continue;
+            }
+
+            if input.len() < 5 {
+                return Err(Base85Error::InvalidLength(value.len()).into());
+            }
+
+            let digits = input[..5].iter().map(|&c| match c {
+                b'!'..=b'u' => Ok(c - ASCII85_OFFSET),
+                _ => Err(Base85Error::InvalidCharacter(c as char)),
+            });
+            group.copy_from_slice(&from_digits(digits)?.to_be_bytes());
+            input = &input[5..];
+        }
+
+        if groups.next().is_some() {
+            return Err(Base85Error::InvalidLength(value.len()).into());
+        }
+
+        Ok(Self::from_bytes(bytes))
+    }
+}
+
+/// Splits a 32-bit value into five base 85 digits, most significant first.
+fn to_digits(mut value: u32) -> impl Iterator<Item = u8> {
+    let mut digits = [0u8; 5];
+    for digit in digits.iter_mut().rev() {
+        *digit = (value % 85) as u8;
+        value /= 85;
+    }
+    digits.into_iter()
+}
+
+/// Combines five base 85 digits, most significant first, into a 32-bit value.
+fn from_digits<I>(digits: I) -> Result<u32, Base85Error>
+where
+    I: Iterator<Item = Result<u8, Base85Error>>,
+{
+    let mut value = 0u64;
+    for digit in digits {
+        value = value * 85 + digit? as u64;
+    }
+    u32::try_from(value).map_err(|_| Base85Error::Overflow)
+}
+
+/// A Z85 or Ascii85 specific parsing error.
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum Base85Error {
+    /// The input had an invalid length.
+    /// The contained value is the actual size.
+    InvalidLength(usize),
+    /// The input contained a character outside the alphabet.
+    InvalidCharacter(char),
+    /// A group of five characters encoded a value larger than 32 bits.
+    Overflow,
+}
+
+impl From<Base85Error> for ParseError {
+    fn from(value: Base85Error) -> Self {
+        Self::InvalidBase85(value)
+    }
+}
+
+impl Debug for Base85Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl Display for Base85Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Base85Error::InvalidLength(len) => write!(f, "invalid length of {len} characters"),
+            Base85Error::InvalidCharacter(c) => write!(f, "invalid character {c:?}"),
+            Base85Error::Overflow => write!(f, "character group exceeds 32 bits"),
+        }
+    }
+}
+
+impl Error for Base85Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn z85_reference_vector_works() {
+        // The example given in ZeroMQ RFC 32, repeated to 16 bytes.
+        let id = ShortGuid::from_bytes([
+            0x86, 0x4F, 0xD2, 0x6F, 0xB5, 0x59, 0xF7, 0x5B, 0x86, 0x4F, 0xD2, 0x6F, 0xB5, 0x59,
+            0xF7, 0x5B,
+        ]);
+        assert_eq!(id.encode_z85(), "HelloWorldHelloWorld");
+        assert_eq!(ShortGuid::parse_z85("HelloWorldHelloWorld").unwrap(), id);
+    }
+
+    #[test]
+    fn ascii85_reference_vector_works() {
+        let id = ShortGuid::from_bytes(*b"Man Man \0\0\0\0Man ");
+        assert_eq!(id.encode_ascii85(), "9jqo^9jqo^z9jqo^");
+        assert_eq!(ShortGuid::parse_ascii85("9jqo^9jqo^z9jqo^").unwrap(), id);
+        assert_eq!(
+            ShortGuid::parse_ascii85("<~9jqo^9jqo^z9jqo^~>").unwrap(),
+            id
+        );
+        assert_eq!(
+            ShortGuid::parse_ascii85("9jqo^9jqo^!!!!!9jqo^").unwrap(),
+            id
+        );
+    }
+
+    #[test]
+    fn roundtrip_works() {
+        for value in [
+            "00000000-0000-0000-0000-000000000000",
+            "ffffffff-ffff-ffff-ffff-ffffffffffff",
+            "c9a646d3-9c61-4cb7-bfcd-ee2522c8f633",
+        ] {
+            let id = ShortGuid::try_parse(value).unwrap();
+            assert_eq!(ShortGuid::parse_z85(id.encode_z85()).unwrap(), id);
+            assert_eq!(ShortGuid::parse_ascii85(id.encode_ascii85()).unwrap(), id);
+        }
+    }
+
+    #[test]
+    fn parse_invalid_input_fails() {
+        assert_eq!(
+            ShortGuid::parse_z85("HelloWorld").unwrap_err(),
+            ParseError::InvalidBase85(Base85Error::InvalidLength(10))
+        );
+        assert_eq!(
+            ShortGuid::parse_z85("HelloWorldHelloWorl\"").unwrap_err(),
+            ParseError::InvalidBase85(Base85Error::InvalidCharacter('"'))
+        );
+        assert_eq!(
+            ShortGuid::parse_z85("HelloWorldHello#####").unwrap_err(),
+            ParseError::InvalidBase85(Base85Error::Overflow)
+        );
+        assert_eq!(
+            ShortGuid::parse_ascii85("zzz").unwrap_err(),
+            ParseError::InvalidBase85(Base85Error::InvalidLength(3))
+        );
+        assert_eq!(
+            ShortGuid::parse_ascii85("zzzzz").unwrap_err(),
+            ParseError::InvalidBase85(Base85Error::InvalidLength(5))
+        );
+        assert_eq!(
+            ShortGuid::parse_ascii85("zzz9jqo~").unwrap_err(),
+            ParseError::InvalidBase85(Base85Error::InvalidCharacter('~'))
+        );
+        assert_eq!(
+            ShortGuid::parse_ascii85("zzzuuuuu").unwrap_err(),
+            ParseError::InvalidBase85(Base85Error::Overflow)
+        );
+    }
+}
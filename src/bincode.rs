@@ -0,0 +1,101 @@
+use crate::ShortGuid;
+use bincode::de::{BorrowDecoder, Decoder};
+use bincode::enc::Encoder;
+use bincode::error::{DecodeError, EncodeError};
+use bincode::{BorrowDecode, Decode, Encode};
+
+/// Encodes the [`ShortGuid`] as exactly 16 raw bytes, without a length prefix.
+impl Encode for ShortGuid {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.as_bytes().encode(encoder)
+    }
+}
+
+impl<Context> Decode<Context> for ShortGuid {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let bytes = <[u8; 16]>::decode(decoder)?;
+        Ok(Self::from_bytes(bytes))
+    }
+}
+
+impl<'de, Context> BorrowDecode<'de, Context> for ShortGuid {
+    fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
+        decoder: &mut D,
+    ) -> Result<Self, DecodeError> {
+        Self::decode(decoder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bincode::config;
+
+    const BYTES: [u8; 16] = *b"F9168C5E-CEB2-4F";
+
+    #[derive(Encode, Decode, Debug, Eq, PartialEq)]
+    struct Record {
+        id: ShortGuid,
+        parents: Vec<ShortGuid>,
+        count: u32,
+    }
+
+    #[derive(bincode::BorrowDecode, Debug, Eq, PartialEq)]
+    struct Borrowed<'a> {
+        id: ShortGuid,
+        name: &'a str,
+    }
+
+    #[test]
+    fn encodes_raw_bytes() {
+        let id = ShortGuid::from_bytes(BYTES);
+        for encoded in [
+            bincode::encode_to_vec(id, config::standard()).unwrap(),
+            bincode::encode_to_vec(id, config::legacy()).unwrap(),
+        ] {
+            assert_eq!(encoded, BYTES);
+        }
+    }
+
+    #[test]
+    fn roundtrip_works() {
+        let record = Record {
+            id: ShortGuid::from_bytes(BYTES),
+            parents: vec![ShortGuid::default(), ShortGuid::from_bytes(BYTES)],
+            count: 3,
+        };
+
+        let encoded = bincode::encode_to_vec(&record, config::standard()).unwrap();
+        assert_eq!(encoded.len(), 16 + 1 + 2 * 16 + 1);
+        let (decoded, read) =
+            bincode::decode_from_slice::<Record, _>(&encoded, config::standard()).unwrap();
+        assert_eq!((decoded, read), (record, encoded.len()));
+
+        let record = Record {
+            id: ShortGuid::from_bytes(BYTES),
+            parents: vec![],
+            count: 0,
+        };
+        let encoded = bincode::encode_to_vec(&record, config::legacy()).unwrap();
+        assert_eq!(encoded.len(), 16 + 8 + 4);
+        let (decoded, _) =
+            bincode::decode_from_slice::<Record, _>(&encoded, config::legacy()).unwrap();
+        assert_eq!(decoded, record);
+    }
+
+    #[test]
+    fn borrow_decode_works() {
+        let mut encoded = BYTES.to_vec();
+        encoded.extend_from_slice(&[5, b'o', b'r', b'd', b'e', b'r']);
+        let (decoded, _) =
+            bincode::borrow_decode_from_slice::<Borrowed, _>(&encoded, config::standard()).unwrap();
+        assert_eq!(decoded.id, ShortGuid::from_bytes(BYTES));
+        assert_eq!(decoded.name, "order");
+    }
+
+    #[test]
+    fn decode_short_input_fails() {
+        let result = bincode::decode_from_slice::<ShortGuid, _>(&BYTES[..15], config::standard());
+        assert!(matches!(result, Err(DecodeError::UnexpectedEnd { .. })));
+    }
+}
@@ -0,0 +1,135 @@
+//! A borsh representation of [`ShortGuid`] as its 22 character string form.
+//!
+//! By default, a [`ShortGuid`] is stored as 16 raw bytes. Use the functions of this module
+//! with `#[borsh(serialize_with, deserialize_with)]` for protocols that carry the encoded ID
+//! as a borsh `String`, i.e. a little-endian `u32` length followed by the UTF-8 bytes.
+//!
+//! ```
+//! use borsh::{BorshDeserialize, BorshSerialize};
+//! use shortguid::ShortGuid;
+//!
+//! #[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+//! struct Order {
+//!     #[borsh(
+//!         serialize_with = "shortguid::borsh_string::serialize",
+//!         deserialize_with = "shortguid::borsh_string::deserialize"
+//!     )]
+//!     id: ShortGuid,
+//! }
+//!
+//! let order = Order { id: ShortGuid::try_parse("yaZG05xhTLe_ze4lIsj2Mw").unwrap() };
+//! let bytes = borsh::to_vec(&order).unwrap();
+//! assert_eq!(&bytes[..4], &22u32.to_le_bytes());
+//! assert_eq!(&bytes[4..], b"yaZG05xhTLe_ze4lIsj2Mw");
+//! assert_eq!(borsh::from_slice::<Order>(&bytes).unwrap(), order);
+//! ```
+//!
+//! With the `borsh-schema` feature, the [`schema`] module describes the field as a `String`
+//! via `#[borsh(schema(with_funcs(...)))]`.
+
+use crate::{Format, ShortGuid};
+use borsh::io::{Error, ErrorKind, Read, Write};
+use borsh::{BorshDeserialize, BorshSerialize};
+
+/// Serializes the [`ShortGuid`] as a borsh `String`.
+pub fn serialize<W: Write>(id: &ShortGuid, writer: &mut W) -> Result<(), Error> {
+    id.to_string().serialize(writer)
+}
+
+/// Deserializes a [`ShortGuid`] from a borsh `String` holding its 22 character form.
+/// The empty string is rejected.
+pub fn deserialize<R: Read>(reader: &mut R) -> Result<ShortGuid, Error> {
+    let value = String::deserialize_reader(reader)?;
+    Format::Short
+        .parse(&value)
+        .map_err(|e| Error::new(ErrorKind::InvalidData, e.to_string()))
+}
+
+/// Schema functions describing the string form, for use with
+/// `#[borsh(schema(with_funcs(declaration = "...", definitions = "...")))]`.
+#[cfg(feature = "borsh-schema")]
+#[cfg_attr(docsrs, doc(cfg(feature = "borsh-schema")))]
+pub mod schema {
+    use borsh::schema::{Declaration, Definition};
+    use borsh::BorshSchema;
+    use std::collections::BTreeMap;
+
+    /// Returns the declaration of a borsh `String`.
+    pub fn declaration() -> Declaration {
+        String::declaration()
+    }
+
+    /// Adds the definition of a borsh `String`.
+    pub fn add_definitions_recursively(definitions: &mut BTreeMap<Declaration, Definition>) {
+        String::add_definitions_recursively(definitions);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHORT: &str = "yaZG05xhTLe_ze4lIsj2Mw";
+
+    #[derive(BorshSerialize, BorshDeserialize, Debug, Eq, PartialEq)]
+    struct Record {
+        #[borsh(serialize_with = "serialize", deserialize_with = "deserialize")]
+        id: ShortGuid,
+        raw: ShortGuid,
+    }
+
+    #[test]
+    fn roundtrip_works() {
+        let id = ShortGuid::try_parse(SHORT).unwrap();
+        let record = Record { id, raw: id };
+
+        let bytes = borsh::to_vec(&record).unwrap();
+        assert_eq!(bytes.len(), 4 + 22 + 16);
+        assert_eq!(&bytes[4..26], SHORT.as_bytes());
+        assert_eq!(&bytes[26..], id.as_bytes());
+        assert_eq!(borsh::from_slice::<Record>(&bytes).unwrap(), record);
+    }
+
+    #[test]
+    fn invalid_string_fails() {
+        for value in [
+            "",
+            "yaZG05xhTLe_ze4lIsj2Mx",
+            "c9a646d3-9c61-4cb7-bfcd-ee2522c8f633",
+        ] {
+            let mut bytes = borsh::to_vec(value).unwrap();
+            bytes.extend_from_slice(&[0; 16]);
+            let error = borsh::from_slice::<Record>(&bytes).unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::InvalidData);
+        }
+    }
+
+    #[cfg(feature = "borsh-schema")]
+    #[test]
+    fn schema_describes_string() {
+        use borsh::schema::{BorshSchemaContainer, Definition, Fields};
+
+        #[derive(borsh::BorshSchema)]
+        #[allow(dead_code)]
+        struct Record {
+            #[borsh(schema(with_funcs(
+                declaration = "schema::declaration",
+                definitions = "schema::add_definitions_recursively"
+            )))]
+            id: ShortGuid,
+            raw: ShortGuid,
+        }
+
+        let container = BorshSchemaContainer::for_type::<Record>();
+        assert_eq!(
+            container.get_definition("Record"),
+            Some(&Definition::Struct {
+                fields: Fields::NamedFields(vec![
+                    ("id".to_string(), "String".to_string()),
+                    ("raw".to_string(), "ShortGuid".to_string()),
+                ])
+            })
+        );
+        assert_eq!(container.validate(), Ok(()));
+    }
+}
@@ -0,0 +1,259 @@
+use crate::{ParseError, ShortGuid};
+use bson::spec::{BinarySubtype, ElementType};
+use bson::{Binary, Bson, UuidRepresentation};
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+
+impl ShortGuid {
+    /// Converts this [`ShortGuid`] into a BSON [`Binary`] with the UUID subtype 4,
+    /// which is how MongoDB drivers store UUIDs by default.
+    ///
+    /// ```
+    /// # use shortguid::ShortGuid;
+    /// use bson::spec::BinarySubtype;
+    ///
+    /// let id = ShortGuid::try_parse("c9a646d3-9c61-4cb7-bfcd-ee2522c8f633").unwrap();
+    /// let binary = id.to_bson_binary();
+    /// assert_eq!(binary.subtype, BinarySubtype::Uuid);
+    /// assert_eq!(binary.bytes, id.as_bytes());
+    /// ```
+    pub fn to_bson_binary(&self) -> Binary {
+        Binary {
+            subtype: BinarySubtype::Uuid,
+            bytes: self.as_bytes().to_vec(),
+        }
+    }
+
+    /// Reads a [`ShortGuid`] from a BSON [`Binary`] in the given representation.
+    ///
+    /// [`UuidRepresentation::Standard`] expects the UUID subtype 4. The legacy representations
+    /// expect the subtype 3 and undo the byte order used by the respective driver:
+    ///
+    /// * [`UuidRepresentation::CSharpLegacy`] stores the first three groups in little-endian order,
+    /// * [`UuidRepresentation::JavaLegacy`] stores both 8 byte halves in reversed order,
+    /// * [`UuidRepresentation::PythonLegacy`] stores the bytes in their regular order.
+    ///
+    /// ```
+    /// # use shortguid::ShortGuid;
+    /// use bson::spec::BinarySubtype;
+    /// use bson::{Binary, UuidRepresentation};
+    ///
+    /// let binary = Binary {
+    ///     subtype: BinarySubtype::UuidOld,
+    ///     bytes: hex::decode("d346a6c9619cb74cbfcdee2522c8f633").unwrap(),
+    /// };
+    /// let id = ShortGuid::from_bson_binary(&binary, UuidRepresentation::CSharpLegacy).unwrap();
+    /// assert_eq!(id.to_string(), "yaZG05xhTLe_ze4lIsj2Mw");
+    /// ```
+    pub fn from_bson_binary(
+        binary: &Binary,
+        representation: UuidRepresentation,
+    ) -> Result<Self, ParseError> {
+        let expected = match representation {
+            UuidRepresentation::Standard => BinarySubtype::Uuid,
+            _ => BinarySubtype::UuidOld,
+        };
+        if binary.subtype != expected {
+            return Err(BsonError::InvalidSubtype(binary.subtype.into()).into());
+        }
+
+        let mut bytes: [u8; 16] = binary
+            .bytes
+            .as_slice()
+            .try_into()
+            .map_err(|_| BsonError::InvalidLength(binary.bytes.len()))?;
+        match representation {
+            UuidRepresentation::CSharpLegacy => {
+                bytes[0..4].reverse();
+                bytes[4..6].reverse();
+                bytes[6..8].reverse();
+            }
+            UuidRepresentation::JavaLegacy => {
+                bytes[0..8].reverse();
+                bytes[8..16].reverse();
+            }
+            _ => {}
+        }
+        Ok(Self::from_bytes(bytes))
+    }
+}
+
+impl From<ShortGuid> for Binary {
+    fn from(value: ShortGuid) -> Self {
+        value.to_bson_binary()
+    }
+}
+
+impl From<ShortGuid> for Bson {
+    fn from(value: ShortGuid) -> Self {
+        Bson::Binary(value.to_bson_binary())
+    }
+}
+
+impl TryFrom<&Binary> for ShortGuid {
+    type Error = ParseError;
+
+    fn try_from(value: &Binary) -> Result<Self, Self::Error> {
+        Self::from_bson_binary(value, UuidRepresentation::Standard)
+    }
+}
+
+impl TryFrom<Binary> for ShortGuid {
+    type Error = ParseError;
+
+    fn try_from(value: Binary) -> Result<Self, Self::Error> {
+        Self::try_from(&value)
+    }
+}
+
+impl TryFrom<&Bson> for ShortGuid {
+    type Error = ParseError;
+
+    fn try_from(value: &Bson) -> Result<Self, Self::Error> {
+        match value {
+            Bson::Binary(binary) => Self::try_from(binary),
+            other => Err(BsonError::InvalidType(other.element_type()).into()),
+        }
+    }
+}
+
+impl TryFrom<Bson> for ShortGuid {
+    type Error = ParseError;
+
+    fn try_from(value: Bson) -> Result<Self, Self::Error> {
+        Self::try_from(&value)
+    }
+}
+
+/// A BSON specific conversion error.
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum BsonError {
+    /// The value was not a binary.
+    /// The contained value is the actual element type.
+    InvalidType(ElementType),
+    /// The binary had an unexpected subtype.
+    /// The contained value is the actual subtype.
+    InvalidSubtype(u8),
+    /// The binary had an invalid length.
+    /// The contained value is the actual number of bytes.
+    InvalidLength(usize),
+}
+
+impl From<BsonError> for ParseError {
+    fn from(value: BsonError) -> Self {
+        Self::InvalidBson(value)
+    }
+}
+
+impl Debug for BsonError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl Display for BsonError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BsonError::InvalidType(element_type) => {
+                write!(
+                    f,
+                    "invalid type; expected a binary, but got {element_type:?}"
+                )
+            }
+            BsonError::InvalidSubtype(subtype) => {
+                write!(f, "invalid binary subtype {subtype:#04x}")
+            }
+            BsonError::InvalidLength(len) => {
+                write!(f, "invalid length; expected 16 bytes, but got {len}")
+            }
+        }
+    }
+}
+
+impl Error for BsonError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UUID: &str = "c9a646d3-9c61-4cb7-bfcd-ee2522c8f633";
+
+    fn binary(subtype: BinarySubtype, hex: &str) -> Binary {
+        Binary {
+            subtype,
+            bytes: hex::decode(hex).unwrap(),
+        }
+    }
+
+    #[test]
+    fn conversion_works() {
+        let id = ShortGuid::try_parse(UUID).unwrap();
+        let value = Bson::from(id);
+        assert_eq!(
+            value,
+            Bson::Binary(binary(
+                BinarySubtype::Uuid,
+                "c9a646d39c614cb7bfcdee2522c8f633"
+            ))
+        );
+        assert_eq!(ShortGuid::try_from(&value).unwrap(), id);
+        assert_eq!(ShortGuid::try_from(Binary::from(id)).unwrap(), id);
+    }
+
+    #[test]
+    fn legacy_representations_work() {
+        // Reference byte orders of the C#, Java and Python drivers.
+        let id = ShortGuid::try_parse(UUID).unwrap();
+        for (representation, hex) in [
+            (
+                UuidRepresentation::CSharpLegacy,
+                "d346a6c9619cb74cbfcdee2522c8f633",
+            ),
+            (
+                UuidRepresentation::JavaLegacy,
+                "b74c619cd346a6c933f6c82225eecdbf",
+            ),
+            (
+                UuidRepresentation::PythonLegacy,
+                "c9a646d39c614cb7bfcdee2522c8f633",
+            ),
+        ] {
+            let legacy = binary(BinarySubtype::UuidOld, hex);
+            assert_eq!(
+                ShortGuid::from_bson_binary(&legacy, representation).unwrap(),
+                id,
+                "{representation:?}"
+            );
+
+            let expected = bson::Uuid::from_bytes(*id.as_bytes());
+            let reference = Binary::from_uuid_with_representation(expected, representation);
+            assert_eq!(reference, legacy, "{representation:?}");
+        }
+    }
+
+    #[test]
+    fn invalid_values_fail() {
+        let generic = binary(BinarySubtype::Generic, "c9a646d39c614cb7bfcdee2522c8f633");
+        assert_eq!(
+            ShortGuid::try_from(&generic).unwrap_err(),
+            ParseError::InvalidBson(BsonError::InvalidSubtype(0))
+        );
+
+        let legacy = binary(BinarySubtype::UuidOld, "c9a646d39c614cb7bfcdee2522c8f633");
+        assert_eq!(
+            ShortGuid::try_from(&legacy).unwrap_err(),
+            ParseError::InvalidBson(BsonError::InvalidSubtype(3))
+        );
+
+        let short = binary(BinarySubtype::Uuid, "c9a646d3");
+        assert_eq!(
+            ShortGuid::try_from(&short).unwrap_err(),
+            ParseError::InvalidBson(BsonError::InvalidLength(4))
+        );
+
+        assert_eq!(
+            ShortGuid::try_from(Bson::String(UUID.to_string())).unwrap_err(),
+            ParseError::InvalidBson(BsonError::InvalidType(ElementType::String))
+        );
+    }
+}
@@ -0,0 +1,730 @@
+//! Bulk encoding and decoding of many IDs at once.
+//!
+//! [`encode_many`] and [`decode_many`] convert between [`ShortGuid`]s and their concatenated
+//! 22 character forms, e.g. for columns or buffers holding millions of IDs. On `x86_64`,
+//! they use AVX2 or SSSE3 instructions if the CPU supports them, and a portable
+//! table-driven implementation otherwise. The results are identical to encoding and
+//! decoding each ID on its own.
+//!
+//! With the `rayon` feature, [`par_encode_many`] and [`par_decode_many`] additionally
+//! split large batches across threads.
+//!
+//! ```
+//! use shortguid::{bulk, ShortGuid};
+//!
+//! let ids = vec![ShortGuid::new_random(), ShortGuid::new_random()];
+//! let mut encoded = vec![0u8; ids.len() * bulk::ENCODED_LEN];
+//! bulk::encode_many(&ids, &mut encoded);
+//! assert_eq!(&encoded[..22], ids[0].to_string().as_bytes());
+//!
+//! let mut decoded = vec![ShortGuid::default(); ids.len()];
+//! bulk::decode_many(&encoded, &mut decoded).unwrap();
+//! assert_eq!(decoded, ids);
+//! ```
+
+use crate::{ParseError, ShortGuid};
+
+/// The length of an encoded [`ShortGuid`].
+pub const ENCODED_LEN: usize = 22;
+
+/// The URL-safe Base64 alphabet.
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Marks characters outside of the alphabet in [`DECODE`].
+const INVALID: u8 = 0xff;
+
+/// Maps each character to its 6-bit value, or [`INVALID`].
+const DECODE: [u8; 256] = {
+    let mut table = [INVALID; 256];
+    let mut i = 0;
+    while i < ALPHABET.len() {
+        table[ALPHABET[i] as usize] = i as u8;
+        i += 1;
+    }
+    table
+};
+
+/// Encodes the IDs into `out` as consecutive 22 character strings.
+///
+/// ## Panics
+/// Panics if `out` is not exactly `ids.len() * 22` bytes long.
+pub fn encode_many(ids: &[ShortGuid], out: &mut [u8]) {
+    assert_eq!(
+        out.len(),
+        ids.len() * ENCODED_LEN,
+        "the output must hold 22 bytes per ID"
+    );
+    Kernel::detect().encode(ids, out);
+}
+
+/// Decodes consecutive 22 character strings from `input` into `out`.
+///
+/// All items are decoded, even if some of them fail. Items that fail to decode are set
+/// to the nil value, and their index and error are reported in the order of the items.
+/// The errors are the same as those of [`ShortGuid::try_parse`] for the item.
+///
+/// ```
+/// use shortguid::{bulk, ShortGuid};
+///
+/// let input = b"yaZG05xhTLe_ze4lIsj2MwNothing to see here...";
+/// let mut decoded = [ShortGuid::new_random(); 2];
+/// let errors = bulk::decode_many(input, &mut decoded).unwrap_err();
+///
+/// assert_eq!(decoded[0].to_string(), "yaZG05xhTLe_ze4lIsj2Mw");
+/// assert!(decoded[1].is_empty());
+/// assert_eq!(errors.len(), 1);
+/// assert_eq!(errors[0].0, 1);
+/// ```
+///
+/// ## Panics
+/// Panics if `input` is not exactly `out.len() * 22` bytes long.
+pub fn decode_many(input: &[u8], out: &mut [ShortGuid]) -> Result<(), Vec<(usize, ParseError)>> {
+    assert_eq!(
+        input.len(),
+        out.len() * ENCODED_LEN,
+        "the input must hold 22 bytes per ID"
+    );
+    let mut errors = Vec::new();
+    Kernel::detect().decode(input, out, 0, &mut errors);
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// The number of IDs each thread processes at a time.
+#[cfg(feature = "rayon")]
+const PARALLEL_CHUNK_LEN: usize = 16 * 1024;
+
+/// Like [`encode_many`], but splits the IDs across threads using `rayon`.
+///
+/// ## Panics
+/// Panics if `out` is not exactly `ids.len() * 22` bytes long.
+#[cfg(feature = "rayon")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+pub fn par_encode_many(ids: &[ShortGuid], out: &mut [u8]) {
+    use rayon::prelude::*;
+
+    assert_eq!(
+        out.len(),
+        ids.len() * ENCODED_LEN,
+        "the output must hold 22 bytes per ID"
+    );
+    let kernel = Kernel::detect();
+    ids.par_chunks(PARALLEL_CHUNK_LEN)
+        .zip(out.par_chunks_mut(PARALLEL_CHUNK_LEN * ENCODED_LEN))
+        .for_each(|(ids, out)| kernel.encode(ids, out));
+}
+
+/// Like [`decode_many`], but splits the input across threads using `rayon`.
+///
+/// ## Panics
+/// Panics if `input` is not exactly `out.len() * 22` bytes long.
+#[cfg(feature = "rayon")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+pub fn par_decode_many(
+    input: &[u8],
+    out: &mut [ShortGuid],
+) -> Result<(), Vec<(usize, ParseError)>> {
+    use rayon::prelude::*;
+
+    assert_eq!(
+        input.len(),
+        out.len() * ENCODED_LEN,
+        "the input must hold 22 bytes per ID"
+    );
+    let kernel = Kernel::detect();
+    let errors: Vec<_> = input
+        .par_chunks(PARALLEL_CHUNK_LEN * ENCODED_LEN)
+        .zip(out.par_chunks_mut(PARALLEL_CHUNK_LEN))
+        .enumerate()
+        .flat_map_iter(|(chunk, (input, out))| {
+            let mut errors = Vec::new();
+            kernel.decode(input, out, chunk * PARALLEL_CHUNK_LEN, &mut errors);
+            errors
+        })
+        .collect();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// The implementation used for encoding and decoding.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Kernel {
+    /// The portable table-driven implementation.
+    Scalar,
+    /// Processes one ID at a time using SSSE3 instructions.
+    #[cfg(target_arch = "x86_64")]
+    Ssse3,
+    /// Processes two IDs at a time using AVX2 instructions.
+    #[cfg(target_arch = "x86_64")]
+    Avx2,
+}
+
+impl Kernel {
+    /// Returns the fastest implementation supported by the CPU.
+    fn detect() -> Self {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if std::is_x86_feature_detected!("avx2") {
+                return Kernel::Avx2;
+            }
+            if std::is_x86_feature_detected!("ssse3") {
+                return Kernel::Ssse3;
+            }
+        }
+        Kernel::Scalar
+    }
+
+    fn encode(self, ids: &[ShortGuid], out: &mut [u8]) {
+        debug_assert_eq!(out.len(), ids.len() * ENCODED_LEN);
+        match self {
+            Kernel::Scalar => {
+                for (id, out) in ids.iter().zip(out.chunks_exact_mut(ENCODED_LEN)) {
+                    encode_scalar(id.as_bytes(), out);
+                }
+            }
+            // SAFETY: The kernel was selected after detecting the CPU feature.
+            #[cfg(target_arch = "x86_64")]
+            Kernel::Ssse3 => unsafe { x86::encode_ssse3(ids, out) },
+            // SAFETY: The kernel was selected after detecting the CPU feature.
+            #[cfg(target_arch = "x86_64")]
+            Kernel::Avx2 => unsafe { x86::encode_avx2(ids, out) },
+        }
+    }
+
+    /// Decodes the input into `out`, reporting errors with indexes relative to `offset`.
+    fn decode(
+        self,
+        input: &[u8],
+        out: &mut [ShortGuid],
+        offset: usize,
+        errors: &mut Vec<(usize, ParseError)>,
+    ) {
+        debug_assert_eq!(input.len(), out.len() * ENCODED_LEN);
+        let mut report = |index: usize, chars: &[u8]| {
+            let error = match ShortGuid::try_decode_bytes(chars) {
+                Err(error) => error,
+                Ok(_) => unreachable!("the fast path rejected a valid ID"),
+            };
+            errors.push((offset + index, error));
+            ShortGuid::default()
+        };
+
+        match self {
+            Kernel::Scalar => {
+                for (i, (chars, out)) in input
+                    .chunks_exact(ENCODED_LEN)
+                    .zip(out.iter_mut())
+                    .enumerate()
+                {
+                    *out = match decode_scalar(chars) {
+                        Some(bytes) => ShortGuid::from_bytes(bytes),
+                        None => report(i, chars),
+                    };
+                }
+            }
+            // SAFETY: The kernel was selected after detecting the CPU feature.
+            #[cfg(target_arch = "x86_64")]
+            Kernel::Ssse3 => unsafe { x86::decode_ssse3(input, out, &mut report) },
+            // SAFETY: The kernel was selected after detecting the CPU feature.
+            #[cfg(target_arch = "x86_64")]
+            Kernel::Avx2 => unsafe { x86::decode_avx2(input, out, &mut report) },
+        }
+    }
+}
+
+/// Encodes the 16 bytes into 22 characters.
+fn encode_scalar(bytes: &[u8; 16], out: &mut [u8]) {
+    for (input, out) in bytes[..15].chunks_exact(3).zip(out.chunks_exact_mut(4)) {
+        encode_group(input, out);
+    }
+    encode_last(bytes[15], &mut out[20..22]);
+}
+
+/// Encodes three bytes into four characters.
+#[inline]
+fn encode_group(input: &[u8], out: &mut [u8]) {
+    let (a, b, c) = (input[0], input[1], input[2]);
+    out[0] = ALPHABET[(a >> 2) as usize];
+    out[1] = ALPHABET[((a & 0x03) << 4 | b >> 4) as usize];
+    out[2] = ALPHABET[((b & 0x0f) << 2 | c >> 6) as usize];
+    out[3] = ALPHABET[(c & 0x3f) as usize];
+}
+
+/// Encodes the last byte into the last two characters.
+#[inline]
+fn encode_last(byte: u8, out: &mut [u8]) {
+    out[0] = ALPHABET[(byte >> 2) as usize];
+    out[1] = ALPHABET[((byte & 0x03) << 4) as usize];
+}
+
+/// Decodes 22 characters into 16 bytes, or returns `None` if they are not a valid ID.
+fn decode_scalar(chars: &[u8]) -> Option<[u8; 16]> {
+    let mut bytes = [0u8; 16];
+    for (input, out) in chars[..20].chunks_exact(4).zip(bytes.chunks_exact_mut(3)) {
+        decode_group(input, out)?;
+    }
+    bytes[15] = decode_last(&chars[20..22])?;
+    Some(bytes)
+}
+
+/// Decodes four characters into three bytes.
+#[inline]
+fn decode_group(input: &[u8], out: &mut [u8]) -> Option<()> {
+    let a = DECODE[input[0] as usize];
+    let b = DECODE[input[1] as usize];
+    let c = DECODE[input[2] as usize];
+    let d = DECODE[input[3] as usize];
+    if (a | b | c | d) & 0xc0 != 0 {
+        return None;
+    }
+    out[0] = a << 2 | b >> 4;
+    out[1] = b << 4 | c >> 2;
+    out[2] = c << 6 | d;
+    Some(())
+}
+
+/// Decodes the last two characters into the last byte, rejecting set trailing bits.
+#[inline]
+fn decode_last(input: &[u8]) -> Option<u8> {
+    let a = DECODE[input[0] as usize];
+    let b = DECODE[input[1] as usize];
+    if (a | b) & 0xc0 != 0 || b & 0x0f != 0 {
+        return None;
+    }
+    Some(a << 2 | b >> 4)
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    //! SSSE3 and AVX2 kernels based on the vectorized Base64 algorithms by Wojciech Muła
+    //! and Daniel Lemire. The vector registers cover the first 12 bytes and 16 characters
+    //! of each ID; the remaining 4 bytes and 6 characters are handled by the scalar code.
+
+    use super::{decode_group, decode_last, encode_group, encode_last, ENCODED_LEN};
+    use crate::ShortGuid;
+    use std::arch::x86_64::*;
+
+    /// Encodes one ID at a time.
+    #[target_feature(enable = "ssse3")]
+    pub(super) unsafe fn encode_ssse3(ids: &[ShortGuid], out: &mut [u8]) {
+        for (id, out) in ids.iter().zip(out.chunks_exact_mut(ENCODED_LEN)) {
+            let input = _mm_loadu_si128(id.as_bytes().as_ptr().cast());
+            let chars = encode_128(input);
+            _mm_storeu_si128(out.as_mut_ptr().cast(), chars);
+            encode_tail(id.as_bytes(), out);
+        }
+    }
+
+    /// Encodes two IDs at a time, and a remaining odd ID using SSSE3.
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn encode_avx2(ids: &[ShortGuid], out: &mut [u8]) {
+        let pairs = ids.chunks_exact(2);
+        let remainder = pairs.remainder();
+        let mut outs = out.chunks_exact_mut(2 * ENCODED_LEN);
+        for (pair, out) in pairs.zip(&mut outs) {
+            let low = _mm_loadu_si128(pair[0].as_bytes().as_ptr().cast());
+            let high = _mm_loadu_si128(pair[1].as_bytes().as_ptr().cast());
+            let chars = encode_256(_mm256_inserti128_si256::<1>(
+                _mm256_castsi128_si256(low),
+                high,
+            ));
+
+            let (first, second) = out.split_at_mut(ENCODED_LEN);
+            _mm_storeu_si128(first.as_mut_ptr().cast(), _mm256_castsi256_si128(chars));
+            _mm_storeu_si128(
+                second.as_mut_ptr().cast(),
+                _mm256_extracti128_si256::<1>(chars),
+            );
+            encode_tail(pair[0].as_bytes(), first);
+            encode_tail(pair[1].as_bytes(), second);
+        }
+        encode_ssse3(remainder, outs.into_remainder());
+    }
+
+    /// Decodes one ID at a time, calling `report` for the items that fail.
+    #[target_feature(enable = "ssse3")]
+    pub(super) unsafe fn decode_ssse3<F>(input: &[u8], out: &mut [ShortGuid], report: &mut F)
+    where
+        F: FnMut(usize, &[u8]) -> ShortGuid,
+    {
+        for (i, (chars, out)) in input
+            .chunks_exact(ENCODED_LEN)
+            .zip(out.iter_mut())
+            .enumerate()
+        {
+            let (bytes, valid) = decode_128(_mm_loadu_si128(chars.as_ptr().cast()));
+            *out = match decode_tail(bytes, chars) {
+                Some(bytes) if valid => ShortGuid::from_bytes(bytes),
+                _ => report(i, chars),
+            };
+        }
+    }
+
+    /// Decodes two IDs at a time, and a remaining odd ID using SSSE3.
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn decode_avx2<F>(input: &[u8], out: &mut [ShortGuid], report: &mut F)
+    where
+        F: FnMut(usize, &[u8]) -> ShortGuid,
+    {
+        let mut outs = out.chunks_exact_mut(2);
+        let pairs = input.chunks_exact(2 * ENCODED_LEN);
+        let remainder = pairs.remainder();
+        let mut index = 0;
+        for (chars, out) in pairs.zip(&mut outs) {
+            let (first, second) = chars.split_at(ENCODED_LEN);
+            let low = _mm_loadu_si128(first.as_ptr().cast());
+            let high = _mm_loadu_si128(second.as_ptr().cast());
+            let (bytes, valid) = decode_256(_mm256_inserti128_si256::<1>(
+                _mm256_castsi128_si256(low),
+                high,
+            ));
+
+            out[0] = match decode_tail(_mm256_castsi256_si128(bytes), first) {
+                Some(bytes) if valid & 0x0000_ffff == 0x0000_ffff => ShortGuid::from_bytes(bytes),
+                _ => report(index, first),
+            };
+            out[1] = match decode_tail(_mm256_extracti128_si256::<1>(bytes), second) {
+                Some(bytes) if valid & 0xffff_0000 == 0xffff_0000 => ShortGuid::from_bytes(bytes),
+                _ => report(index + 1, second),
+            };
+            index += 2;
+        }
+
+        let mut report = |i: usize, chars: &[u8]| report(index + i, chars);
+        decode_ssse3(remainder, outs.into_remainder(), &mut report);
+    }
+
+    /// Encodes the last 4 bytes of the ID into the last 6 characters.
+    #[inline]
+    fn encode_tail(bytes: &[u8; 16], out: &mut [u8]) {
+        encode_group(&bytes[12..15], &mut out[16..20]);
+        encode_last(bytes[15], &mut out[20..22]);
+    }
+
+    /// Completes the 12 bytes decoded from the first 16 characters with the last 4 bytes.
+    #[inline]
+    unsafe fn decode_tail(head: __m128i, chars: &[u8]) -> Option<[u8; 16]> {
+        let mut bytes = [0u8; 16];
+        _mm_storeu_si128(bytes.as_mut_ptr().cast(), head);
+        decode_group(&chars[16..20], &mut bytes[12..15])?;
+        bytes[15] = decode_last(&chars[20..22])?;
+        Some(bytes)
+    }
+
+    /// Encodes the first 12 bytes into 16 characters.
+    #[inline]
+    #[target_feature(enable = "ssse3")]
+    unsafe fn encode_128(input: __m128i) -> __m128i {
+        // Spread each group of three bytes across four 6-bit indices.
+        let input = _mm_shuffle_epi8(
+            input,
+            _mm_setr_epi8(1, 0, 2, 1, 4, 3, 5, 4, 7, 6, 8, 7, 10, 9, 11, 10),
+        );
+        let t0 = _mm_and_si128(input, _mm_set1_epi32(0x0fc0_fc00));
+        let t1 = _mm_mulhi_epu16(t0, _mm_set1_epi32(0x0400_0040));
+        let t2 = _mm_and_si128(input, _mm_set1_epi32(0x003f_03f0));
+        let t3 = _mm_mullo_epi16(t2, _mm_set1_epi32(0x0100_0010));
+        let indices = _mm_or_si128(t1, t3);
+
+        // Map each index to the offset of its character range.
+        let mut ranges = _mm_subs_epu8(indices, _mm_set1_epi8(51));
+        let upper = _mm_cmpgt_epi8(_mm_set1_epi8(26), indices);
+        ranges = _mm_or_si128(ranges, _mm_and_si128(upper, _mm_set1_epi8(13)));
+        let offsets = _mm_setr_epi8(
+            71, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -17, 32, 65, 0, 0,
+        );
+        _mm_add_epi8(indices, _mm_shuffle_epi8(offsets, ranges))
+    }
+
+    /// Encodes the first 12 bytes of both lanes into 16 characters each.
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn encode_256(input: __m256i) -> __m256i {
+        let input = _mm256_shuffle_epi8(
+            input,
+            _mm256_broadcastsi128_si256(_mm_setr_epi8(
+                1, 0, 2, 1, 4, 3, 5, 4, 7, 6, 8, 7, 10, 9, 11, 10,
+            )),
+        );
+        let t0 = _mm256_and_si256(input, _mm256_set1_epi32(0x0fc0_fc00));
+        let t1 = _mm256_mulhi_epu16(t0, _mm256_set1_epi32(0x0400_0040));
+        let t2 = _mm256_and_si256(input, _mm256_set1_epi32(0x003f_03f0));
+        let t3 = _mm256_mullo_epi16(t2, _mm256_set1_epi32(0x0100_0010));
+        let indices = _mm256_or_si256(t1, t3);
+
+        let mut ranges = _mm256_subs_epu8(indices, _mm256_set1_epi8(51));
+        let upper = _mm256_cmpgt_epi8(_mm256_set1_epi8(26), indices);
+        ranges = _mm256_or_si256(ranges, _mm256_and_si256(upper, _mm256_set1_epi8(13)));
+        let offsets = _mm256_broadcastsi128_si256(_mm_setr_epi8(
+            71, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -17, 32, 65, 0, 0,
+        ));
+        _mm256_add_epi8(indices, _mm256_shuffle_epi8(offsets, ranges))
+    }
+
+    /// Decodes 16 characters into 12 bytes, and tests whether all characters are valid.
+    #[inline]
+    #[target_feature(enable = "ssse3")]
+    unsafe fn decode_128(chars: __m128i) -> (__m128i, bool) {
+        let in_range = |lo: u8, hi: u8| {
+            _mm_and_si128(
+                _mm_cmpgt_epi8(chars, _mm_set1_epi8(lo as i8 - 1)),
+                _mm_cmpgt_epi8(_mm_set1_epi8(hi as i8 + 1), chars),
+            )
+        };
+        let upper = in_range(b'A', b'Z');
+        let lower = in_range(b'a', b'z');
+        let digit = in_range(b'0', b'9');
+        let dash = _mm_cmpeq_epi8(chars, _mm_set1_epi8(b'-' as i8));
+        let underscore = _mm_cmpeq_epi8(chars, _mm_set1_epi8(b'_' as i8));
+
+        let valid = _mm_or_si128(
+            _mm_or_si128(_mm_or_si128(upper, lower), _mm_or_si128(digit, dash)),
+            underscore,
+        );
+        let offsets = _mm_or_si128(
+            _mm_or_si128(
+                _mm_and_si128(upper, _mm_set1_epi8(-65)),
+                _mm_and_si128(lower, _mm_set1_epi8(-71)),
+            ),
+            _mm_or_si128(
+                _mm_or_si128(
+                    _mm_and_si128(digit, _mm_set1_epi8(4)),
+                    _mm_and_si128(dash, _mm_set1_epi8(17)),
+                ),
+                _mm_and_si128(underscore, _mm_set1_epi8(-32)),
+            ),
+        );
+        let indices = _mm_add_epi8(chars, offsets);
+
+        // Merge four 6-bit indices into three bytes each.
+        let merged = _mm_maddubs_epi16(indices, _mm_set1_epi32(0x0140_0140));
+        let merged = _mm_madd_epi16(merged, _mm_set1_epi32(0x0001_1000));
+        let bytes = _mm_shuffle_epi8(
+            merged,
+            _mm_setr_epi8(2, 1, 0, 6, 5, 4, 10, 9, 8, 14, 13, 12, -1, -1, -1, -1),
+        );
+        (bytes, _mm_movemask_epi8(valid) == 0xffff)
+    }
+
+    /// Decodes 16 characters of both lanes into 12 bytes each, and returns the mask of
+    /// valid characters.
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn decode_256(chars: __m256i) -> (__m256i, u32) {
+        let in_range = |lo: u8, hi: u8| {
+            _mm256_and_si256(
+                _mm256_cmpgt_epi8(chars, _mm256_set1_epi8(lo as i8 - 1)),
+                _mm256_cmpgt_epi8(_mm256_set1_epi8(hi as i8 + 1), chars),
+            )
+        };
+        let upper = in_range(b'A', b'Z');
+        let lower = in_range(b'a', b'z');
+        let digit = in_range(b'0', b'9');
+        let dash = _mm256_cmpeq_epi8(chars, _mm256_set1_epi8(b'-' as i8));
+        let underscore = _mm256_cmpeq_epi8(chars, _mm256_set1_epi8(b'_' as i8));
+
+        let valid = _mm256_or_si256(
+            _mm256_or_si256(_mm256_or_si256(upper, lower), _mm256_or_si256(digit, dash)),
+            underscore,
+        );
+        let offsets = _mm256_or_si256(
+            _mm256_or_si256(
+                _mm256_and_si256(upper, _mm256_set1_epi8(-65)),
+                _mm256_and_si256(lower, _mm256_set1_epi8(-71)),
+            ),
+            _mm256_or_si256(
+                _mm256_or_si256(
+                    _mm256_and_si256(digit, _mm256_set1_epi8(4)),
+                    _mm256_and_si256(dash, _mm256_set1_epi8(17)),
+                ),
+                _mm256_and_si256(underscore, _mm256_set1_epi8(-32)),
+            ),
+        );
+        let indices = _mm256_add_epi8(chars, offsets);
+
+        let merged = _mm256_maddubs_epi16(indices, _mm256_set1_epi32(0x0140_0140));
+        let merged = _mm256_madd_epi16(merged, _mm256_set1_epi32(0x0001_1000));
+        let bytes = _mm256_shuffle_epi8(
+            merged,
+            _mm256_broadcastsi128_si256(_mm_setr_epi8(
+                2, 1, 0, 6, 5, 4, 10, 9, 8, 14, 13, 12, -1, -1, -1, -1,
+            )),
+        );
+        (bytes, _mm256_movemask_epi8(valid) as u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns all kernels supported by the CPU.
+    fn kernels() -> Vec<Kernel> {
+        let mut kernels = vec![Kernel::Scalar];
+        #[cfg(target_arch = "x86_64")]
+        {
+            if std::is_x86_feature_detected!("ssse3") {
+                kernels.push(Kernel::Ssse3);
+            }
+            if std::is_x86_feature_detected!("avx2") {
+                kernels.push(Kernel::Avx2);
+            }
+        }
+        kernels
+    }
+
+    /// Returns pseudo-random bytes.
+    fn random_bytes(len: usize) -> Vec<u8> {
+        std::iter::repeat_with(|| *ShortGuid::new_random().as_bytes())
+            .flatten()
+            .take(len)
+            .collect()
+    }
+
+    /// Returns a mix of valid and corrupted encoded IDs.
+    fn test_input(count: usize) -> Vec<u8> {
+        let noise = random_bytes(count * 4);
+        let mut input = Vec::with_capacity(count * ENCODED_LEN);
+        for i in 0..count {
+            let mut chars = ShortGuid::new_random().to_string().into_bytes();
+            match noise[i * 4] % 4 {
+                // Replace a character with an arbitrary byte.
+                0 => chars[noise[i * 4 + 1] as usize % ENCODED_LEN] = noise[i * 4 + 2],
+                // Replace the last character with any character of the alphabet.
+                1 => chars[21] = ALPHABET[noise[i * 4 + 1] as usize % 64],
+                _ => {}
+            }
+            input.extend_from_slice(&chars);
+        }
+        input
+    }
+
+    #[test]
+    fn encode_matches_scalar() {
+        for count in [0, 1, 2, 3, 17, 1000] {
+            let ids: Vec<_> = (0..count).map(|_| ShortGuid::new_random()).collect();
+            let expected: String = ids.iter().map(ShortGuid::to_string).collect();
+
+            for kernel in kernels() {
+                let mut out = vec![0u8; count * ENCODED_LEN];
+                kernel.encode(&ids, &mut out);
+                assert_eq!(out, expected.as_bytes(), "{kernel:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn encode_edge_values_match_scalar() {
+        let ids: Vec<_> = (0..=255u8)
+            .map(|b| ShortGuid::from_bytes([b; 16]))
+            .collect();
+        let expected: String = ids.iter().map(ShortGuid::to_string).collect();
+        for kernel in kernels() {
+            let mut out = vec![0u8; ids.len() * ENCODED_LEN];
+            kernel.encode(&ids, &mut out);
+            assert_eq!(out, expected.as_bytes(), "{kernel:?}");
+        }
+    }
+
+    #[test]
+    fn decode_matches_scalar() {
+        for count in [0, 1, 2, 3, 17, 1000] {
+            let input = test_input(count);
+            let expected: Vec<_> = input
+                .chunks_exact(ENCODED_LEN)
+                .map(ShortGuid::try_decode_bytes)
+                .collect();
+
+            for kernel in kernels() {
+                let mut out = vec![ShortGuid::new_random(); count];
+                let mut errors = Vec::new();
+                kernel.decode(&input, &mut out, 10, &mut errors);
+
+                let mut errors = errors.into_iter().peekable();
+                for (i, expected) in expected.iter().enumerate() {
+                    match expected {
+                        Ok(uuid) => assert_eq!(out[i], *uuid, "{kernel:?} {i}"),
+                        Err(error) => {
+                            assert!(out[i].is_empty(), "{kernel:?} {i}");
+                            let (index, actual) = errors.next().expect("missing error");
+                            assert_eq!(index, i + 10, "{kernel:?}");
+                            assert_eq!(actual, *error, "{kernel:?}");
+                        }
+                    }
+                }
+                assert_eq!(errors.next(), None, "{kernel:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn decode_random_bytes_matches_scalar() {
+        let input = random_bytes(100 * ENCODED_LEN);
+        for kernel in kernels() {
+            let mut out = vec![ShortGuid::default(); 100];
+            let mut errors = Vec::new();
+            kernel.decode(&input, &mut out, 0, &mut errors);
+            assert_eq!(errors.len(), 100, "{kernel:?}");
+            for (i, error) in errors {
+                let chars = &input[i * ENCODED_LEN..(i + 1) * ENCODED_LEN];
+                assert_eq!(Err(error), ShortGuid::try_decode_bytes(chars));
+            }
+        }
+    }
+
+    #[test]
+    fn decode_many_works() {
+        let mut input = b"yaZG05xhTLe_ze4lIsj2Mw".repeat(3);
+        input[ENCODED_LEN + 21] = b'x';
+        let mut out = [ShortGuid::new_random(); 3];
+
+        let errors = decode_many(&input, &mut out).unwrap_err();
+        assert_eq!(
+            errors,
+            [(
+                1,
+                ShortGuid::try_parse("yaZG05xhTLe_ze4lIsj2Mx").unwrap_err()
+            )]
+        );
+        assert_eq!(out[0], out[2]);
+        assert!(out[1].is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "the output must hold 22 bytes per ID")]
+    fn encode_many_checks_length() {
+        encode_many(&[ShortGuid::default()], &mut [0u8; 21]);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_matches_sequential() {
+        let count = PARALLEL_CHUNK_LEN * 2 + 5;
+        let input = test_input(count);
+
+        let mut sequential = vec![ShortGuid::default(); count];
+        let mut parallel = vec![ShortGuid::default(); count];
+        let expected = decode_many(&input, &mut sequential);
+        assert_eq!(par_decode_many(&input, &mut parallel), expected);
+        assert_eq!(parallel, sequential);
+
+        let mut sequential = vec![0u8; count * ENCODED_LEN];
+        let mut parallel = vec![0u8; count * ENCODED_LEN];
+        encode_many(&sequential_ids(&input), &mut sequential);
+        par_encode_many(&sequential_ids(&input), &mut parallel);
+        assert_eq!(parallel, sequential);
+    }
+
+    #[cfg(feature = "rayon")]
+    fn sequential_ids(input: &[u8]) -> Vec<ShortGuid> {
+        let mut ids = vec![ShortGuid::default(); input.len() / ENCODED_LEN];
+        let _ = decode_many(input, &mut ids);
+        ids
+    }
+}
@@ -0,0 +1,396 @@
+//! Diesel support for [`ShortGuid`](crate::ShortGuid) columns.
+//!
+//! The backend features map IDs to the native column types: Postgres `Uuid`, MySQL `Binary`
+//! holding the 16 raw bytes, and SQLite `Binary` or `Text`. SQLite `Text` columns store the
+//! 22 character string and also read the hyphenated form, but reject empty text rather
+//! than reading it as the nil ID.
+
+#[cfg(feature = "diesel-postgres")]
+mod postgres {
+    use crate::ShortGuid;
+    use diesel::deserialize::{self, FromSql};
+    use diesel::pg::{Pg, PgValue};
+    use diesel::serialize::{self, IsNull, Output, ToSql};
+    use diesel::sql_types::Uuid;
+    use std::io::Write;
+
+    impl FromSql<Uuid, Pg> for ShortGuid {
+        fn from_sql(value: PgValue<'_>) -> deserialize::Result<Self> {
+            Ok(ShortGuid::from_slice(value.as_bytes())?)
+        }
+    }
+
+    impl ToSql<Uuid, Pg> for ShortGuid {
+        fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+            out.write_all(self.as_bytes())?;
+            Ok(IsNull::No)
+        }
+    }
+}
+
+#[cfg(feature = "diesel-mysql")]
+mod mysql {
+    use crate::ShortGuid;
+    use diesel::deserialize::{self, FromSql};
+    use diesel::mysql::{Mysql, MysqlValue};
+    use diesel::serialize::{self, IsNull, Output, ToSql};
+    use diesel::sql_types::Binary;
+    use std::io::Write;
+
+    impl FromSql<Binary, Mysql> for ShortGuid {
+        fn from_sql(value: MysqlValue<'_>) -> deserialize::Result<Self> {
+            Ok(ShortGuid::from_slice(value.as_bytes())?)
+        }
+    }
+
+    impl ToSql<Binary, Mysql> for ShortGuid {
+        fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Mysql>) -> serialize::Result {
+            out.write_all(self.as_bytes())?;
+            Ok(IsNull::No)
+        }
+    }
+}
+
+#[cfg(feature = "diesel-sqlite")]
+mod sqlite {
+    use crate::ShortGuid;
+    use diesel::deserialize::{self, FromSql};
+    use diesel::serialize::{self, IsNull, Output, ToSql};
+    use diesel::sql_types::{Binary, Text};
+    use diesel::sqlite::{Sqlite, SqliteValue};
+
+    impl FromSql<Binary, Sqlite> for ShortGuid {
+        fn from_sql(value: SqliteValue<'_, '_, '_>) -> deserialize::Result<Self> {
+            let bytes = <Vec<u8> as FromSql<Binary, Sqlite>>::from_sql(value)?;
+            Ok(ShortGuid::from_slice(bytes)?)
+        }
+    }
+
+    impl ToSql<Binary, Sqlite> for ShortGuid {
+        fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Sqlite>) -> serialize::Result {
+            out.set_value(self.as_bytes().as_slice());
+            Ok(IsNull::No)
+        }
+    }
+
+    /// Reads the 22 character string, or any other non-empty format accepted by
+    /// [`ShortGuid::try_parse`].
+    impl FromSql<Text, Sqlite> for ShortGuid {
+        fn from_sql(value: SqliteValue<'_, '_, '_>) -> deserialize::Result<Self> {
+            let value = <String as FromSql<Text, Sqlite>>::from_sql(value)?;
+            Ok(ShortGuid::try_parse_non_empty(&value)?)
+        }
+    }
+
+    /// Writes the 22 character string.
+    impl ToSql<Text, Sqlite> for ShortGuid {
+        fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Sqlite>) -> serialize::Result {
+            out.set_value(self.to_string());
+            Ok(IsNull::No)
+        }
+    }
+}
+
+#[cfg(all(test, feature = "diesel-sqlite"))]
+mod sqlite_tests {
+    use crate::ShortGuid;
+    use diesel::prelude::*;
+    use diesel::sql_types::{Binary, Text};
+
+    diesel::table! {
+        users (id) {
+            id -> Binary,
+            name -> Text,
+            invited_by -> Nullable<Text>,
+        }
+    }
+
+    #[derive(Debug, PartialEq, Queryable, Insertable, Selectable)]
+    #[diesel(table_name = users)]
+    struct User {
+        id: ShortGuid,
+        name: String,
+        invited_by: Option<ShortGuid>,
+    }
+
+    fn connect() -> SqliteConnection {
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        diesel::sql_query(
+            "CREATE TABLE users (id BLOB PRIMARY KEY, name TEXT NOT NULL, invited_by TEXT)",
+        )
+        .execute(&mut conn)
+        .unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_insert_and_load() {
+        let mut conn = connect();
+        let alice = User {
+            id: ShortGuid::new_random(),
+            name: "alice".into(),
+            invited_by: None,
+        };
+        let bob = User {
+            id: ShortGuid::new_random(),
+            name: "bob".into(),
+            invited_by: Some(alice.id),
+        };
+        diesel::insert_into(users::table)
+            .values([&alice, &bob])
+            .execute(&mut conn)
+            .unwrap();
+
+        let loaded: User = users::table
+            .filter(users::id.eq(bob.id))
+            .select(User::as_select())
+            .first(&mut conn)
+            .unwrap();
+        assert_eq!(loaded, bob);
+
+        let invited: Vec<ShortGuid> = users::table
+            .filter(users::invited_by.eq(alice.id))
+            .select(users::id)
+            .load(&mut conn)
+            .unwrap();
+        assert_eq!(invited, [bob.id]);
+    }
+
+    #[test]
+    fn test_column_types() {
+        let mut conn = connect();
+        let id = ShortGuid::try_parse("yaZG05xhTLe_ze4lIsj2Mw").unwrap();
+        diesel::insert_into(users::table)
+            .values((
+                users::id.eq(id),
+                users::name.eq("alice"),
+                users::invited_by.eq(id),
+            ))
+            .execute(&mut conn)
+            .unwrap();
+
+        let (blob, text): (Vec<u8>, Option<String>) = users::table
+            .select((users::id, users::invited_by))
+            .first(&mut conn)
+            .unwrap();
+        assert_eq!(blob, id.as_bytes());
+        assert_eq!(text.as_deref(), Some("yaZG05xhTLe_ze4lIsj2Mw"));
+    }
+
+    #[test]
+    fn test_decode() {
+        let mut conn = connect();
+        let id: ShortGuid = diesel::select(diesel::dsl::sql::<Text>(
+            "'c9a646d3-9c61-4cb7-bfcd-ee2522c8f633'",
+        ))
+        .get_result(&mut conn)
+        .unwrap();
+        assert_eq!(id, "yaZG05xhTLe_ze4lIsj2Mw");
+
+        let result = diesel::select(diesel::dsl::sql::<Binary>("x'c9a646d3'"))
+            .get_result::<ShortGuid>(&mut conn);
+        assert!(result.is_err());
+
+        let result = diesel::select(diesel::dsl::sql::<Text>("'yaZG05xhTLe'"))
+            .get_result::<ShortGuid>(&mut conn);
+        assert!(result.is_err());
+
+        let result =
+            diesel::select(diesel::dsl::sql::<Text>("''")).get_result::<ShortGuid>(&mut conn);
+        assert!(result.is_err());
+    }
+}
+
+#[cfg(all(test, feature = "diesel-postgres"))]
+mod postgres_tests {
+    use crate::ShortGuid;
+    use diesel::pg::Pg;
+    use diesel::prelude::*;
+    use diesel::sql_types::Text;
+    use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
+
+    diesel::table! {
+        users (id) {
+            id -> Uuid,
+            name -> Text,
+            invited_by -> Nullable<Uuid>,
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq, Queryable, Insertable, Selectable)]
+    #[diesel(table_name = users)]
+    struct User {
+        id: ShortGuid,
+        name: String,
+        invited_by: Option<ShortGuid>,
+    }
+
+    /// Connects to the Postgres server in `DATABASE_URL`.
+    async fn connect() -> AsyncPgConnection {
+        let url = std::env::var("DATABASE_URL")
+            .expect("DATABASE_URL must point to a Postgres server to run these tests");
+        assert!(
+            url.starts_with("postgres"),
+            "DATABASE_URL is not a Postgres URL: {url}"
+        );
+        let mut conn = AsyncPgConnection::establish(&url).await.unwrap();
+        diesel::sql_query(
+            "CREATE TEMPORARY TABLE users (id UUID PRIMARY KEY, name TEXT NOT NULL, invited_by UUID)",
+        )
+        .execute(&mut conn)
+        .await
+        .unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_as_expression() {
+        let id = ShortGuid::new_random();
+        let query = users::table.filter(users::id.eq(id)).select(users::id);
+        let sql = diesel::debug_query::<Pg, _>(&query).to_string();
+        assert!(sql.contains(r#"WHERE ("users"."id" = $1)"#), "{sql}");
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a Postgres server in DATABASE_URL"]
+    async fn test_roundtrip() {
+        let mut conn = connect().await;
+        let alice = User {
+            id: ShortGuid::new_random(),
+            name: "alice".into(),
+            invited_by: None,
+        };
+        let bob = User {
+            id: ShortGuid::new_random(),
+            name: "bob".into(),
+            invited_by: Some(alice.id),
+        };
+        diesel::insert_into(users::table)
+            .values([alice.clone(), bob.clone()])
+            .execute(&mut conn)
+            .await
+            .unwrap();
+
+        let loaded: User = users::table
+            .filter(users::id.eq(bob.id))
+            .select(User::as_select())
+            .first(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(loaded, bob);
+
+        let invited: Vec<ShortGuid> = users::table
+            .filter(users::invited_by.eq(alice.id))
+            .select(users::id)
+            .load(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(invited, [bob.id]);
+
+        let text: String = users::table
+            .filter(users::id.eq(alice.id))
+            .select(diesel::dsl::sql::<Text>("id::text"))
+            .first(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(text, alice.id.as_uuid().to_string());
+    }
+}
+
+#[cfg(all(test, feature = "diesel-mysql"))]
+mod mysql_tests {
+    use crate::ShortGuid;
+    use diesel::prelude::*;
+    use diesel::sql_types::{Binary, Text};
+    use diesel_async::{AsyncConnection, AsyncMysqlConnection, RunQueryDsl};
+
+    diesel::table! {
+        users (id) {
+            id -> Binary,
+            name -> Text,
+            invited_by -> Nullable<Binary>,
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq, Queryable, Insertable, Selectable)]
+    #[diesel(table_name = users)]
+    struct User {
+        id: ShortGuid,
+        name: String,
+        invited_by: Option<ShortGuid>,
+    }
+
+    /// Connects to the MySQL server in `DATABASE_URL`.
+    async fn connect() -> AsyncMysqlConnection {
+        let url = std::env::var("DATABASE_URL")
+            .expect("DATABASE_URL must point to a MySQL server to run these tests");
+        assert!(
+            url.starts_with("mysql"),
+            "DATABASE_URL is not a MySQL URL: {url}"
+        );
+        let mut conn = AsyncMysqlConnection::establish(&url).await.unwrap();
+        diesel::sql_query(
+            "CREATE TEMPORARY TABLE users \
+             (id BINARY(16) PRIMARY KEY, name TEXT NOT NULL, invited_by BINARY(16))",
+        )
+        .execute(&mut conn)
+        .await
+        .unwrap();
+        conn
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a MySQL server in DATABASE_URL"]
+    async fn test_roundtrip() {
+        let mut conn = connect().await;
+        let alice = User {
+            id: ShortGuid::new_random(),
+            name: "alice".into(),
+            invited_by: None,
+        };
+        let bob = User {
+            id: ShortGuid::new_random(),
+            name: "bob".into(),
+            invited_by: Some(alice.id),
+        };
+        diesel::insert_into(users::table)
+            .values([alice.clone(), bob.clone()])
+            .execute(&mut conn)
+            .await
+            .unwrap();
+
+        let loaded: User = users::table
+            .filter(users::id.eq(bob.id))
+            .select(User::as_select())
+            .first(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(loaded, bob);
+
+        let invited: Vec<ShortGuid> = users::table
+            .filter(users::invited_by.eq(alice.id))
+            .select(users::id)
+            .load(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(invited, [bob.id]);
+
+        let hex: String = users::table
+            .filter(users::id.eq(alice.id))
+            .select(diesel::dsl::sql::<Text>("HEX(id)"))
+            .first(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(hex, hex::encode_upper(alice.id.as_bytes()));
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a MySQL server in DATABASE_URL"]
+    async fn test_decode() {
+        let mut conn = connect().await;
+        let result = diesel::select(diesel::dsl::sql::<Binary>("x'c9a646d3'"))
+            .get_result::<ShortGuid>(&mut conn)
+            .await;
+        assert!(result.is_err());
+    }
+}
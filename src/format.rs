@@ -0,0 +1,164 @@
+use crate::{ParseError, ShortGuid};
+use uuid::Uuid;
+
+/// A textual representation of a [`ShortGuid`].
+///
+/// Used with [`ShortGuid::try_parse_with`] to opt into representations beyond the
+/// ShortGuid and UUID strings accepted by [`ShortGuid::try_parse`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Format {
+    /// Any UUID string representation, such as `c9a646d3-9c61-4cb7-bfcd-ee2522c8f633`.
+    Uuid,
+    /// The 22 character URL-safe Base64 representation, such as `yaZG05xhTLe_ze4lIsj2Mw`.
+    ///
+    /// Unlike [`ShortGuid::try_parse`], parsing rejects the empty string rather than
+    /// treating it as the nil ID.
+    Short,
+    /// The 24 character Base45 representation; see [`ShortGuid::encode_base45`].
+    Base45,
+    /// The 20 character Z85 representation; see [`ShortGuid::encode_z85`].
+    Z85,
+    /// The Ascii85 representation; see [`ShortGuid::encode_ascii85`].
+    Ascii85,
+}
+
+impl Format {
+    /// The formats accepted by [`ShortGuid::try_parse`].
+    ///
+    /// Unlike [`ShortGuid::try_parse`], parsing with these formats rejects the empty
+    /// string rather than treating it as the nil ID.
+    pub const DEFAULT: &'static [Format] = &[Format::Uuid, Format::Short];
+
+    /// Parses the value in this format.
+    pub fn parse<S: AsRef<str>>(self, value: S) -> Result<ShortGuid, ParseError> {
+        let value = value.as_ref();
+        match self {
+            Format::Uuid => Uuid::try_parse(value)
+                .map(ShortGuid::from)
+                .map_err(ParseError::InvalidUuid),
+            Format::Short if value.is_empty() => Err(ParseError::InvalidLength(0)),
+            Format::Short => ShortGuid::try_decode(value).map(ShortGuid::from),
+            Format::Base45 => ShortGuid::parse_base45(value),
+            Format::Z85 => ShortGuid::parse_z85(value),
+            Format::Ascii85 => ShortGuid::parse_ascii85(value),
+        }
+    }
+
+    /// Encodes the ID in this format.
+    ///
+    /// UUIDs are encoded in their lowercase hyphenated form.
+    pub fn encode(self, id: &ShortGuid) -> String {
+        match self {
+            Format::Uuid => id.as_uuid().hyphenated().to_string(),
+            Format::Short => id.to_string(),
+            Format::Base45 => id.encode_base45(),
+            Format::Z85 => id.encode_z85(),
+            Format::Ascii85 => id.encode_ascii85(),
+        }
+    }
+}
+
+impl ShortGuid {
+    /// Tries to parse the value in each of the provided formats, in order.
+    ///
+    /// Returns the first successfully parsed value, or the error of the last format
+    /// if none of them matched.
+    ///
+    /// ```
+    /// # use shortguid::{Format, ShortGuid};
+    /// let formats = [Format::Uuid, Format::Short, Format::Z85];
+    /// let id = ShortGuid::try_parse("yaZG05xhTLe_ze4lIsj2Mw").unwrap();
+    /// assert_eq!(ShortGuid::try_parse_with(id.encode_z85(), &formats).unwrap(), id);
+    /// assert_eq!(ShortGuid::try_parse_with("yaZG05xhTLe_ze4lIsj2Mw", &formats).unwrap(), id);
+    /// ```
+    pub fn try_parse_with<S: AsRef<str>>(value: S, formats: &[Format]) -> Result<Self, ParseError> {
+        let value = value.as_ref();
+        let mut error = ParseError::InvalidLength(value.len());
+        for format in formats {
+            match format.parse(value) {
+                Ok(id) => return Ok(id),
+                Err(err) => error = err,
+            }
+        }
+        Err(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Base85Error;
+
+    const UUID: &str = "c9a646d3-9c61-4cb7-bfcd-ee2522c8f633";
+
+    #[test]
+    fn default_formats_match_try_parse() {
+        let id = ShortGuid::try_parse(UUID).unwrap();
+        for value in [UUID.to_string(), id.to_string()] {
+            assert_eq!(
+                ShortGuid::try_parse_with(&value, Format::DEFAULT).unwrap(),
+                id
+            );
+        }
+        assert!(ShortGuid::try_parse_with(id.encode_z85(), Format::DEFAULT).is_err());
+    }
+
+    #[test]
+    fn default_formats_reject_empty() {
+        assert_eq!(ShortGuid::try_parse("").unwrap(), ShortGuid::default());
+        assert!(Format::Short.parse("").is_err());
+        assert!(ShortGuid::try_parse_with("", Format::DEFAULT).is_err());
+    }
+
+    #[test]
+    fn opt_in_formats_work() {
+        let id = ShortGuid::try_parse(UUID).unwrap();
+        let all = [
+            Format::Uuid,
+            Format::Short,
+            Format::Base45,
+            Format::Z85,
+            Format::Ascii85,
+        ];
+        for value in [
+            UUID.to_string(),
+            id.to_string(),
+            id.encode_base45(),
+            id.encode_z85(),
+            id.encode_ascii85(),
+        ] {
+            assert_eq!(ShortGuid::try_parse_with(&value, &all).unwrap(), id);
+        }
+    }
+
+    #[test]
+    fn encode_roundtrips() {
+        let id = ShortGuid::try_parse(UUID).unwrap();
+        assert_eq!(Format::Uuid.encode(&id), UUID);
+        assert_eq!(Format::Short.encode(&id), "yaZG05xhTLe_ze4lIsj2Mw");
+        for format in [Format::Base45, Format::Z85, Format::Ascii85] {
+            assert_eq!(format.parse(format.encode(&id)).unwrap(), id);
+        }
+    }
+
+    #[test]
+    fn short_rejects_empty_string() {
+        assert_eq!(ShortGuid::try_parse("").unwrap(), ShortGuid::default());
+        assert_eq!(
+            Format::Short.parse("").unwrap_err(),
+            ParseError::InvalidLength(0)
+        );
+    }
+
+    #[test]
+    fn last_error_is_returned() {
+        assert_eq!(
+            ShortGuid::try_parse_with("abc", &[Format::Short, Format::Z85]).unwrap_err(),
+            ParseError::InvalidBase85(Base85Error::InvalidLength(3))
+        );
+        assert_eq!(
+            ShortGuid::try_parse_with("abc", &[]).unwrap_err(),
+            ParseError::InvalidLength(3)
+        );
+    }
+}
@@ -15,24 +15,137 @@
 //!
 //! ## Create features
 //!
-//! Other crate features can also be useful beyond the version support:
+//! Other crate features can also be useful beyond the version support. The crate supports
+//! Rust 1.67.1; features whose dependencies need a newer toolchain say so below.
 //!
 //! * `serde` - adds the ability to serialize and deserialize a UUID using `serde`.
+//! * `apache-avro` - adds an `AvroSchemaComponent` implementation using the `uuid` logical type
+//!   and conversions to and from Avro `string` and `fixed(16)` values. Requires Rust 1.88.
+//! * `arrow` - adds the `arrow` module for `FixedSizeBinary(16)` columns with the `arrow.uuid` extension type.
+//!   Requires Rust 1.71.
+//! * `bson` - adds conversions to and from the BSON UUID binary and the `serde::bson_binary` module
+//!   for MongoDB, including the legacy C#, Java and Python representations. Implies `serde`.
+//!   Requires Rust 1.73.
+//! * `cbor` - adds the `serde::cbor` module for the CBOR UUID tag 37 using `ciborium`. Implies `serde`.
+//!   Requires Rust 1.71.
 //! * `borsh` - adds the ability to serialize and deserialize a UUID using `borsh`.
+//!   The `borsh_string` module stores the 22 character string form instead.
+//! * `borsh-schema` - adds a `BorshSchema` implementation using borsh's unstable schema support.
 //! * `arbitrary` - adds an `Arbitrary` trait implementation to `Uuid` for fuzzing.
 //! * `random` - adds the ability to generate a random [`ShortGuid`]s.
 //! * `fast-rng` - uses a faster algorithm for generating random [`ShortGuid`]s.
 //!   This feature requires more dependencies to compile, but is just as suitable for
 //!   [`ShortGuid`] as the default algorithm. Implies `random`, enabled by default.
 //! * `bytemuck` - adds a `Pod` trait implementation to `Uuid` for byte manipulation.
+//! * `bincode` - adds the ability to encode and decode a [`ShortGuid`] using `bincode` 2.
+//!   Requires Rust 1.85.
+//! * `prost` - adds the `proto::ShortGuid` Protocol Buffers message and conversions for
+//!   `bytes` and `string` fields using `prost`. Requires Rust 1.71.
+//! * `rkyv` - adds `rkyv` archive support with validation; see `ArchivedShortGuid`.
+//!   Requires Rust 1.81.
+//! * `zerocopy` - adds the `zerocopy` traits for reinterpreting bytes as [`ShortGuid`]s without copying.
+//! * `derive` - adds the `ShortGuidNewtype` derive macro for newtypes wrapping a [`ShortGuid`].
+//! * `mnemonic` - adds a BIP39 word list representation for reading IDs aloud.
+//! * `sqlx` - adds the `TextShortGuid` wrapper for storing the string form in `TEXT` columns using `sqlx`.
+//!   The `sqlx-postgres`, `sqlx-mysql` and `sqlx-sqlite` features imply `sqlx` and implement `Type`,
+//!   `Encode` and `Decode` for [`ShortGuid`] as Postgres `UUID`, MySQL `BINARY(16)` and SQLite `BLOB`.
+//!   Requires Rust 1.78.
+//! * `diesel` - adds the diesel `AsExpression` and `FromSqlRow` implementations for [`ShortGuid`].
+//!   The `diesel-postgres`, `diesel-mysql` and `diesel-sqlite` features imply `diesel` and implement
+//!   `FromSql` and `ToSql` for Postgres `Uuid`, MySQL `Binary` and SQLite `Binary` and `Text` columns.
+//!   Requires Rust 1.78, or 1.85 with `diesel-sqlite`.
+//! * `rayon` - adds parallel variants of the `bulk` encoding and decoding functions using `rayon`.
 
 // only enables the `doc_cfg` feature when
 // the `docsrs` configuration attribute is defined
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
+mod abbrev;
+#[cfg(feature = "rkyv")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rkyv")))]
+mod archived;
+#[cfg(feature = "arrow")]
+#[cfg_attr(docsrs, doc(cfg(feature = "arrow")))]
+pub mod arrow;
+#[cfg(feature = "apache-avro")]
+#[cfg_attr(docsrs, doc(cfg(feature = "apache-avro")))]
+mod avro;
+mod base45;
+mod base85;
+#[cfg(feature = "bincode")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bincode")))]
+mod bincode;
+#[cfg(feature = "borsh")]
+#[cfg_attr(docsrs, doc(cfg(feature = "borsh")))]
+pub mod borsh_string;
+#[cfg(feature = "bson")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bson")))]
+mod bson;
+pub mod bulk;
+#[cfg(feature = "diesel")]
+#[cfg_attr(docsrs, doc(cfg(feature = "diesel")))]
+mod diesel_sql;
+mod format;
+#[cfg(feature = "mnemonic")]
+#[cfg_attr(docsrs, doc(cfg(feature = "mnemonic")))]
+mod mnemonic;
+mod non_nil;
+#[cfg(feature = "prost")]
+#[cfg_attr(docsrs, doc(cfg(feature = "prost")))]
+pub mod proto;
+pub mod scan;
 #[cfg(feature = "serde")]
 #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
-mod serde;
+pub mod serde;
+#[cfg(feature = "sqlx")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sqlx")))]
+mod sqlx;
+mod typed;
+mod typeid;
+mod version;
+
+pub use abbrev::{AbbrevIndex, ResolveError};
+#[cfg(feature = "apache-avro")]
+#[cfg_attr(docsrs, doc(cfg(feature = "apache-avro")))]
+pub use avro::AvroError;
+pub use base45::Base45Error;
+pub use base85::Base85Error;
+#[cfg(feature = "bson")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bson")))]
+pub use bson::BsonError;
+pub use format::Format;
+#[cfg(feature = "mnemonic")]
+#[cfg_attr(docsrs, doc(cfg(feature = "mnemonic")))]
+pub use mnemonic::MnemonicError;
+pub use non_nil::NonNilShortGuid;
+#[cfg(feature = "sqlx")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sqlx")))]
+pub use sqlx::TextShortGuid;
+pub use typed::{IdPrefix, TypedShortGuid};
+pub use typeid::{TypeId, TypeIdError};
+pub use version::{ShortGuidV4, ShortGuidV7, VersionPolicy, VersionedShortGuid};
+
+#[cfg(feature = "derive")]
+#[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
+pub use shortguid_derive::ShortGuidNewtype;
+
+/// Support items for the code generated by the `ShortGuidNewtype` derive macro.
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::typed::{fmt_prefixed, parse_prefixed};
+    pub use uuid::Uuid;
+
+    #[cfg(feature = "serde")]
+    pub use crate::serde::{deserialize_prefixed, serialize_prefixed};
+    #[cfg(feature = "serde")]
+    pub use serde;
+
+    #[cfg(feature = "borsh")]
+    pub use borsh;
+
+    #[cfg(feature = "bytemuck")]
+    pub use bytemuck;
+}
 
 use base64::{DecodeError, Engine};
 use std::borrow::Borrow;
@@ -67,20 +180,79 @@ use uuid::Uuid;
 /// ```
 #[derive(Default, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-// #[cfg_attr(
-//      feature = "zerocopy",
-//      derive(zerocopy::AsBytes, zerocopy::FromBytes, zerocopy::Unaligned)
-// )]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(
+        zerocopy::FromBytes,
+        zerocopy::IntoBytes,
+        zerocopy::KnownLayout,
+        zerocopy::Immutable,
+        zerocopy::Unaligned
+    )
+)]
 #[cfg_attr(
     feature = "borsh",
     derive(borsh_derive::BorshDeserialize, borsh_derive::BorshSerialize)
 )]
+#[cfg_attr(feature = "bytemuck", derive(bytemuck::Zeroable, bytemuck::Pod))]
+#[cfg_attr(
+    feature = "diesel",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow),
+    diesel(sql_type = diesel::sql_types::Binary),
+    diesel(sql_type = diesel::sql_types::Text)
+)]
+#[cfg_attr(
+    feature = "diesel-postgres",
+    diesel(sql_type = diesel::sql_types::Uuid)
+)]
 #[cfg_attr(
-    feature = "bytemuck",
-    derive(bytemuck::Zeroable, bytemuck::Pod, bytemuck::TransparentWrapper)
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    rkyv(
+        derive(Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd),
+        compare(PartialEq, PartialOrd)
+    )
 )]
 #[repr(transparent)]
-pub struct ShortGuid(Uuid);
+pub struct ShortGuid(uuid::Bytes);
+
+// SAFETY: `ShortGuid` and `Uuid` are both transparent wrappers around `[u8; 16]`.
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::TransparentWrapper<Uuid> for ShortGuid {}
+
+// `as_uuid` and `from_bytes_ref` reinterpret references between `ShortGuid`, `Uuid`
+// and `[u8; 16]`, which requires all three to have the same size and alignment.
+const _: () = {
+    assert!(std::mem::size_of::<ShortGuid>() == std::mem::size_of::<Uuid>());
+    assert!(std::mem::align_of::<ShortGuid>() == std::mem::align_of::<Uuid>());
+    assert!(std::mem::size_of::<ShortGuid>() == std::mem::size_of::<uuid::Bytes>());
+    assert!(std::mem::align_of::<ShortGuid>() == std::mem::align_of::<uuid::Bytes>());
+};
+
+/// Describes a [`ShortGuid`] as a struct wrapping 16 raw bytes, matching its borsh encoding.
+#[cfg(feature = "borsh-schema")]
+#[cfg_attr(docsrs, doc(cfg(feature = "borsh-schema")))]
+impl borsh::BorshSchema for ShortGuid {
+    fn add_definitions_recursively(
+        definitions: &mut std::collections::BTreeMap<
+            borsh::schema::Declaration,
+            borsh::schema::Definition,
+        >,
+    ) {
+        use borsh::schema::{add_definition, Definition, Fields};
+        let fields = Fields::UnnamedFields(vec![uuid::Bytes::declaration()]);
+        add_definition(
+            Self::declaration(),
+            Definition::Struct { fields },
+            definitions,
+        );
+        uuid::Bytes::add_definitions_recursively(definitions);
+    }
+
+    fn declaration() -> borsh::schema::Declaration {
+        "ShortGuid".to_string()
+    }
+}
 
 /// A short UUID format.
 impl ShortGuid {
@@ -95,25 +267,36 @@ impl ShortGuid {
     /// Creates a new [`ShortGuid`] based on the provided [`Uuid`].
     #[inline(always)]
     pub const fn new_from_uuid(uuid: Uuid) -> Self {
-        Self(uuid)
+        Self(uuid.into_bytes())
     }
 
     /// Tries to parse the value as a [`ShortGuid`] or [`Uuid`] string, and outputs an actual
     /// [`ShortGuid`] instance.
+    ///
+    /// Use [`ShortGuid::try_parse_with`] to accept other representations such as Z85.
     pub fn try_parse<S: AsRef<str>>(value: S) -> Result<Self, ParseError> {
         if let Ok(uuid) = Uuid::try_parse(value.as_ref()) {
-            return Ok(Self(uuid));
+            return Ok(Self::new_from_uuid(uuid));
         }
 
         let uuid = Self::try_decode(value)?;
-        Ok(Self(uuid))
+        Ok(Self::new_from_uuid(uuid))
+    }
+
+    /// Like [`ShortGuid::try_parse`], but rejects the empty string instead of
+    /// returning the nil ID, e.g. for values read from text columns or fields.
+    pub(crate) fn try_parse_non_empty(value: &str) -> Result<Self, ParseError> {
+        if value.is_empty() {
+            return Err(ParseError::InvalidLength(0));
+        }
+        Self::try_parse(value)
     }
 
     /// Creates a [`ShortGuid`] using the supplied bytes.
     #[inline]
     pub fn from_slice<B: AsRef<[u8]>>(bytes: B) -> Result<Self, ParseError> {
         let uuid = Uuid::from_slice(bytes.as_ref()).map_err(ParseError::InvalidSlice)?;
-        Ok(Self(uuid))
+        Ok(Self::new_from_uuid(uuid))
     }
 
     /// Constructs a [`ShortGuid`] instance based on a byte slice.
@@ -124,7 +307,7 @@ impl ShortGuid {
     /// instead.
     #[inline]
     pub fn from_bytes<B: Borrow<[u8; 16]>>(bytes: B) -> Self {
-        Self(*Uuid::from_bytes_ref(bytes.borrow()))
+        Self(*bytes.borrow())
     }
 
     /// Constructs a [`ShortGuid`] instance based on a byte slice of bytes ordered in little endian.
@@ -153,7 +336,7 @@ impl ShortGuid {
     /// ```
     #[inline]
     pub fn from_bytes_le<B: Borrow<[u8; 16]>>(bytes: B) -> Self {
-        Self(Uuid::from_bytes_le(*bytes.borrow()))
+        Self::new_from_uuid(Uuid::from_bytes_le(*bytes.borrow()))
     }
 
     /// Returns a slice of 16 octets containing the value.
@@ -184,20 +367,21 @@ impl ShortGuid {
     /// ```
     #[inline]
     pub const fn from_bytes_ref(bytes: &[u8; 16]) -> &Self {
-        // SAFETY: `Bytes`, `Uuid` and `ShortGuid have the same ABI
-        unsafe { &*(bytes as *const [u8; 16] as *const Uuid as *const ShortGuid) }
+        // SAFETY: `ShortGuid` is a transparent wrapper around `[u8; 16]`
+        unsafe { &*(bytes as *const [u8; 16] as *const ShortGuid) }
     }
 
     /// Tests if this [`ShortGuid`] is all zeros.
     #[inline]
     pub const fn is_empty(&self) -> bool {
-        self.0.is_nil()
+        self.as_uuid().is_nil()
     }
 
     /// Returns the underlying [`Uuid`] instance.
     #[inline]
     pub const fn as_uuid(&self) -> &Uuid {
-        &self.0
+        // SAFETY: `Uuid` is a transparent wrapper around `[u8; 16]`
+        unsafe { &*(&self.0 as *const [u8; 16] as *const Uuid) }
     }
 
     /// Returns a slice of 16 octets containing the value.
@@ -227,8 +411,8 @@ impl ShortGuid {
     /// ));
     /// ```
     #[inline]
-    pub fn as_bytes(&self) -> &[u8; 16] {
-        self.0.as_bytes()
+    pub const fn as_bytes(&self) -> &[u8; 16] {
+        &self.0
     }
 
     /// Returns the bytes of the [`ShortGuid`] in little-endian order.
@@ -257,7 +441,7 @@ impl ShortGuid {
     /// ```
     #[inline]
     pub const fn to_bytes_le(&self) -> [u8; 16] {
-        self.0.to_bytes_le()
+        self.as_uuid().to_bytes_le()
     }
 
     /// Decodes the given value to a [`Uuid`].
@@ -265,7 +449,12 @@ impl ShortGuid {
     /// ## Arguments
     /// * `value` - A 22 character ShortGuid URL-safe Base64 string.
     fn try_decode<S: AsRef<str>>(value: S) -> Result<Uuid, ParseError> {
-        let value = value.as_ref();
+        Self::try_decode_bytes(value.as_ref().as_bytes())
+    }
+
+    /// Decodes the given bytes of a 22 character ShortGuid URL-safe Base64 string
+    /// to a [`Uuid`]; see [`ShortGuid::try_decode`].
+    fn try_decode_bytes(value: &[u8]) -> Result<Uuid, ParseError> {
         if value.is_empty() {
             return Ok(Uuid::default());
         }
@@ -310,27 +499,27 @@ impl Debug for ShortGuid {
         write!(
             f,
             "{short} ({long})",
-            short = Self::encode(self.0),
-            long = self.0
+            short = Self::encode(self.as_uuid()),
+            long = self.as_uuid()
         )
     }
 }
 
 impl Display for ShortGuid {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{short}", short = Self::encode(self.0))
+        write!(f, "{short}", short = Self::encode(self.as_uuid()))
     }
 }
 
 impl From<Uuid> for ShortGuid {
     fn from(value: Uuid) -> Self {
-        Self(value)
+        Self::new_from_uuid(value)
     }
 }
 
 impl From<ShortGuid> for Uuid {
     fn from(value: ShortGuid) -> Self {
-        value.0
+        *value.as_uuid()
     }
 }
 
@@ -350,20 +539,28 @@ impl TryFrom<&str> for ShortGuid {
     }
 }
 
+impl TryFrom<Vec<u8>> for ShortGuid {
+    type Error = ParseError;
+
+    fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
+        ShortGuid::from_slice(value)
+    }
+}
+
 impl PartialEq<Uuid> for ShortGuid {
     fn eq(&self, other: &Uuid) -> bool {
-        self.0.eq(other)
+        self.as_uuid().eq(other)
     }
 }
 
 impl PartialEq<String> for ShortGuid {
     fn eq(&self, other: &String) -> bool {
         if let Ok(uuid) = ShortGuid::try_decode(other) {
-            return self.0.eq(&uuid);
+            return self.as_uuid().eq(&uuid);
         }
 
         if let Ok(uuid) = Uuid::try_parse(other) {
-            return self.0.eq(&uuid);
+            return self.as_uuid().eq(&uuid);
         }
 
         false
@@ -373,11 +570,11 @@ impl PartialEq<String> for ShortGuid {
 impl PartialEq<str> for ShortGuid {
     fn eq(&self, other: &str) -> bool {
         if let Ok(uuid) = ShortGuid::try_decode(other) {
-            return self.0.eq(&uuid);
+            return self.as_uuid().eq(&uuid);
         }
 
         if let Ok(uuid) = Uuid::try_parse(other) {
-            return self.0.eq(&uuid);
+            return self.as_uuid().eq(&uuid);
         }
 
         false
@@ -433,7 +630,11 @@ impl AsRef<[u8]> for ShortGuid {
 }
 
 /// A parsing error.
-#[derive(Eq, PartialEq)]
+///
+/// Some variants only exist when the corresponding crate feature is enabled, so this
+/// enum is non-exhaustive to keep the features additive.
+#[derive(PartialEq)]
+#[non_exhaustive]
 pub enum ParseError {
     /// The provided input had an invalid length.
     /// The contained value is the actual size.
@@ -443,8 +644,44 @@ pub enum ParseError {
     InvalidFormat(DecodeError),
     /// The provided slice input was invalid.
     InvalidSlice(uuid::Error),
+    /// The provided input did not start with the expected prefix.
+    /// The contained value is the expected prefix, without the separator.
+    InvalidPrefix { expected: &'static str },
+    /// The provided input was not a valid TypeID.
+    InvalidTypeId(TypeIdError),
+    /// The provided input had an unexpected UUID version.
+    /// The contained value is the actual version.
+    InvalidVersion(usize),
+    /// The provided input was not an RFC 9562 (RFC 4122) UUID.
+    /// The contained value is the actual variant.
+    InvalidVariant(uuid::Variant),
+    /// The provided input was the nil (all zeros) value, which is not allowed here.
+    NilValue,
+    /// The provided input was the max (all ones) value, which is not allowed here.
+    MaxValue,
+    /// The provided input was not a valid Base45 encoded ID.
+    InvalidBase45(Base45Error),
+    /// The provided input was not a valid Z85 or Ascii85 encoded ID.
+    InvalidBase85(Base85Error),
+    /// The provided input was not a valid UUID string.
+    InvalidUuid(uuid::Error),
+    /// The provided input was not a valid mnemonic.
+    #[cfg(feature = "mnemonic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "mnemonic")))]
+    InvalidMnemonic(MnemonicError),
+    /// The provided BSON value was not a valid UUID binary.
+    #[cfg(feature = "bson")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "bson")))]
+    InvalidBson(BsonError),
+    /// The provided Avro value was not a valid UUID.
+    #[cfg(feature = "apache-avro")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "apache-avro")))]
+    InvalidAvro(AvroError),
 }
 
+// `uuid::Variant` only implements `PartialEq`, but its equality is total.
+impl Eq for ParseError {}
+
 impl From<DecodeError> for ParseError {
     fn from(value: DecodeError) -> Self {
         Self::InvalidFormat(value)
@@ -466,6 +703,27 @@ impl Display for ParseError {
             ),
             ParseError::InvalidFormat(err) => write!(f, "Invalid ID format: {err}"),
             ParseError::InvalidSlice(err) => write!(f, "Invalid slice: {err}"),
+            ParseError::InvalidPrefix { expected } => {
+                write!(f, "Invalid ID prefix; expected \"{expected}_\"")
+            }
+            ParseError::InvalidTypeId(err) => write!(f, "Invalid TypeID: {err}"),
+            ParseError::InvalidVersion(version) => {
+                write!(f, "Invalid ID version; got version {version}")
+            }
+            ParseError::InvalidVariant(variant) => {
+                write!(f, "Invalid ID variant; got the {variant} variant")
+            }
+            ParseError::NilValue => write!(f, "Invalid ID; the nil value is not allowed"),
+            ParseError::MaxValue => write!(f, "Invalid ID; the max value is not allowed"),
+            ParseError::InvalidBase45(err) => write!(f, "Invalid Base45 ID: {err}"),
+            ParseError::InvalidBase85(err) => write!(f, "Invalid Base85 ID: {err}"),
+            ParseError::InvalidUuid(err) => write!(f, "Invalid UUID: {err}"),
+            #[cfg(feature = "mnemonic")]
+            ParseError::InvalidMnemonic(err) => write!(f, "Invalid mnemonic: {err}"),
+            #[cfg(feature = "bson")]
+            ParseError::InvalidBson(err) => write!(f, "Invalid BSON binary: {err}"),
+            #[cfg(feature = "apache-avro")]
+            ParseError::InvalidAvro(err) => write!(f, "Invalid Avro value: {err}"),
         }
     }
 }
@@ -652,4 +910,85 @@ mod borsh_tests {
         let deserialized = borsh::from_slice::<Uuid>(&sg_bytes).unwrap().to_string();
         assert_eq!(uuid_str, deserialized);
     }
+
+    #[cfg(feature = "borsh-schema")]
+    #[test]
+    fn test_schema() {
+        use borsh::schema::{BorshSchemaContainer, Definition, Fields};
+
+        let container = BorshSchemaContainer::for_type::<ShortGuid>();
+        assert_eq!(container.declaration(), "ShortGuid");
+        assert_eq!(
+            container.get_definition("ShortGuid"),
+            Some(&Definition::Struct {
+                fields: Fields::UnnamedFields(vec!["[u8; 16]".to_string()])
+            })
+        );
+        assert_eq!(
+            container.get_definition("[u8; 16]"),
+            Some(&Definition::Sequence {
+                length_width: Definition::ARRAY_LENGTH_WIDTH,
+                length_range: 16..=16,
+                elements: "u8".to_string()
+            })
+        );
+        assert_eq!(container.validate(), Ok(()));
+
+        let sg = ShortGuid::from_str("f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4").unwrap();
+        let size = borsh::max_serialized_size::<ShortGuid>().unwrap();
+        assert_eq!(size, borsh::to_vec(&sg).unwrap().len());
+        assert_eq!(size, 16);
+    }
+}
+
+#[cfg(all(test, feature = "zerocopy"))]
+mod zerocopy_tests {
+    use super::*;
+    use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned};
+
+    #[test]
+    fn test_slice_from_bytes() {
+        let mut bytes = [0u8; 48];
+        bytes[..16].copy_from_slice(b"F9168C5E-CEB2-4F");
+        bytes[32..].fill(0xff);
+
+        let ids = <[ShortGuid]>::ref_from_bytes(&bytes[..]).unwrap();
+        assert_eq!(ids.len(), 3);
+        assert_eq!(ids[0], ShortGuid::from_bytes(b"F9168C5E-CEB2-4F"));
+        assert!(ids[1].is_empty());
+        assert_eq!(ids[2], Uuid::max());
+        assert_eq!(ids.as_bytes(), &bytes[..]);
+
+        assert!(<[ShortGuid]>::ref_from_bytes(&bytes[..47]).is_err());
+    }
+
+    #[test]
+    fn test_header_from_prefix() {
+        #[derive(FromBytes, IntoBytes, KnownLayout, Immutable, Unaligned)]
+        #[repr(C)]
+        struct Header {
+            kind: u8,
+            id: ShortGuid,
+            flags: u8,
+        }
+
+        let id = ShortGuid::from_str("f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4").unwrap();
+        let mut packet = vec![7];
+        packet.extend_from_slice(id.as_bytes());
+        packet.extend_from_slice(&[1, 0xde, 0xad]);
+
+        let (header, payload) = Header::ref_from_prefix(&packet).unwrap();
+        assert_eq!(header.kind, 7);
+        assert_eq!(header.id, id);
+        assert_eq!(header.flags, 1);
+        assert_eq!(payload, [0xde, 0xad]);
+        assert!(std::ptr::eq(&header.id.as_bytes()[0], &packet[1]));
+    }
+
+    #[test]
+    fn test_read_from_bytes() {
+        let id = ShortGuid::read_from_bytes(b"F9168C5E-CEB2-4F").unwrap();
+        assert_eq!(id.as_bytes(), b"F9168C5E-CEB2-4F");
+        assert!(ShortGuid::read_from_bytes(&[0; 15]).is_err());
+    }
 }
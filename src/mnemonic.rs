@@ -0,0 +1,312 @@
+use crate::{ParseError, ShortGuid};
+use bip39::{Language, Mnemonic};
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+
+/// The number of words in a mnemonic encoded [`ShortGuid`].
+const WORD_COUNT: usize = 12;
+
+/// The number of leading letters that uniquely identify a word in the BIP39 English list.
+const UNIQUE_PREFIX_LEN: usize = 4;
+
+impl ShortGuid {
+    /// Encodes this [`ShortGuid`] as 12 words from the
+    /// [BIP39](https://github.com/bitcoin/bips/blob/master/bip-0039.mediawiki) English word list.
+    ///
+    /// The 128 bits of the ID are followed by a 4 bit checksum, which allows
+    /// [`ShortGuid::parse_mnemonic`] to detect most transmission errors. This makes the
+    /// representation suitable for reading IDs aloud.
+    ///
+    /// ```
+    /// # use shortguid::ShortGuid;
+    /// let id = ShortGuid::try_parse("c9a646d3-9c61-4cb7-bfcd-ee2522c8f633").unwrap();
+    /// let words = id.to_mnemonic();
+    /// assert_eq!(words.split(' ').count(), 12);
+    /// assert_eq!(ShortGuid::parse_mnemonic(words).unwrap(), id);
+    /// ```
+    pub fn to_mnemonic(&self) -> String {
+        let mnemonic = Mnemonic::from_entropy_in(Language::English, self.as_bytes())
+            .expect("16 bytes are valid entropy");
+        let mut words = String::with_capacity(WORD_COUNT * 9);
+        for (i, word) in mnemonic.words().enumerate() {
+            if i > 0 {
+                words.push(' ');
+            }
+            words.push_str(word);
+        }
+        words
+    }
+
+    /// Parses 12 words as produced by [`ShortGuid::to_mnemonic`].
+    ///
+    /// Parsing is case-insensitive and accepts any whitespace, hyphens or commas between
+    /// the words. Each word may be abbreviated to its first four letters, and a word with a
+    /// single wrong, missing, extra or swapped letter is corrected if the correction is unique.
+    /// Words with more than one possible correction are rejected.
+    /// The checksum is verified after all corrections have been applied.
+    ///
+    /// The 4 bit checksum still accepts one in 16 wrong corrections. Use
+    /// [`ShortGuid::parse_mnemonic_with_corrections`] to learn which words were corrected,
+    /// e.g. to have them confirmed.
+    ///
+    /// ```
+    /// # use shortguid::ShortGuid;
+    /// let id = ShortGuid::try_parse("c9a646d3-9c61-4cb7-bfcd-ee2522c8f633").unwrap();
+    /// let words = id.to_mnemonic().to_uppercase().replace(' ', "  -  ");
+    /// assert_eq!(ShortGuid::parse_mnemonic(words).unwrap(), id);
+    /// ```
+    pub fn parse_mnemonic<S: AsRef<str>>(value: S) -> Result<Self, ParseError> {
+        Self::parse_mnemonic_with_corrections(value).map(|(id, _)| id)
+    }
+
+    /// Parses 12 words like [`ShortGuid::parse_mnemonic`] and additionally returns the
+    /// positions of the words that were corrected for misspellings.
+    ///
+    /// Exact words and four letter abbreviations are unambiguous and not reported.
+    ///
+    /// ```
+    /// # use shortguid::ShortGuid;
+    /// let words = "legal winner thnak year wave saus wurth useful legal winner thank yell";
+    /// let (id, corrected) = ShortGuid::parse_mnemonic_with_corrections(words).unwrap();
+    /// assert_eq!(id, ShortGuid::from_bytes([0x7f; 16]));
+    /// assert_eq!(corrected, [2, 6]);
+    /// ```
+    pub fn parse_mnemonic_with_corrections<S: AsRef<str>>(
+        value: S,
+    ) -> Result<(Self, Vec<usize>), ParseError> {
+        let value = value.as_ref().to_lowercase();
+        let words: Vec<&str> = value
+            .split(|c: char| c.is_whitespace() || c == '-' || c == ',')
+            .filter(|word| !word.is_empty())
+            .collect();
+
+        if words.len() != WORD_COUNT {
+            return Err(MnemonicError::InvalidWordCount(words.len()).into());
+        }
+
+        let mut normalized = String::with_capacity(WORD_COUNT * 9);
+        let mut corrected = Vec::new();
+        for (i, word) in words.into_iter().enumerate() {
+            if i > 0 {
+                normalized.push(' ');
+            }
+            let (resolved, was_corrected) = resolve_word(i, word)?;
+            normalized.push_str(resolved);
+            if was_corrected {
+                corrected.push(i);
+            }
+        }
+
+        let mnemonic = Mnemonic::parse_in_normalized(Language::English, &normalized)
+            .map_err(|_| MnemonicError::InvalidChecksum)?;
+        let (entropy, len) = mnemonic.to_entropy_array();
+        Ok((Self::from_slice(&entropy[..len])?, corrected))
+    }
+}
+
+/// Resolves a possibly abbreviated or misspelled word to its word list entry.
+/// The returned flag indicates whether the word was corrected for a misspelling.
+fn resolve_word(index: usize, word: &str) -> Result<(&'static str, bool), MnemonicError> {
+    let list = Language::English.word_list();
+    if let Some(i) = Language::English.find_word(word) {
+        return Ok((list[i as usize], false));
+    }
+
+    if word.len() >= UNIQUE_PREFIX_LEN {
+        if let [candidate] = Language::English.words_by_prefix(word) {
+            return Ok((candidate, false));
+        }
+    }
+
+    let mut candidates = list.iter().filter(|c| within_one_edit(word, c));
+    match (candidates.next(), candidates.next()) {
+        (Some(candidate), None) => Ok((candidate, true)),
+        (Some(_), Some(_)) => Err(MnemonicError::AmbiguousWord(index)),
+        (None, _) => Err(MnemonicError::UnknownWord(index)),
+    }
+}
+
+/// Tests whether two words differ by at most one substitution, insertion, deletion
+/// or transposition of adjacent letters.
+fn within_one_edit(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let (short, long) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    if long.len() - short.len() > 1 {
+        return false;
+    }
+
+    let prefix = short.iter().zip(long).take_while(|(x, y)| x == y).count();
+    if prefix == short.len() {
+        return true;
+    }
+
+    if short.len() != long.len() {
+        return short[prefix..] == long[prefix + 1..];
+    }
+
+    let substituted = short[prefix + 1..] == long[prefix + 1..];
+    let transposed = prefix + 1 < short.len()
+        && short[prefix] == long[prefix + 1]
+        && short[prefix + 1] == long[prefix]
+        && short[prefix + 2..] == long[prefix + 2..];
+    substituted || transposed
+}
+
+/// A mnemonic specific parsing error.
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum MnemonicError {
+    /// The input had an invalid number of words.
+    /// The contained value is the actual number of words.
+    InvalidWordCount(usize),
+    /// The word at the contained index could not be matched to the word list.
+    UnknownWord(usize),
+    /// The word at the contained index matched more than one word list entry.
+    AmbiguousWord(usize),
+    /// The checksum did not match, which indicates a misheard or mistyped word.
+    InvalidChecksum,
+}
+
+impl From<MnemonicError> for ParseError {
+    fn from(value: MnemonicError) -> Self {
+        Self::InvalidMnemonic(value)
+    }
+}
+
+impl Debug for MnemonicError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl Display for MnemonicError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MnemonicError::InvalidWordCount(count) => write!(
+                f,
+                "invalid word count; expected {WORD_COUNT} words, but got {count}"
+            ),
+            MnemonicError::UnknownWord(index) => write!(f, "unknown word at position {index}"),
+            MnemonicError::AmbiguousWord(index) => {
+                write!(f, "ambiguous word at position {index}")
+            }
+            MnemonicError::InvalidChecksum => write!(f, "checksum mismatch"),
+        }
+    }
+}
+
+impl Error for MnemonicError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UUID: &str = "c9a646d3-9c61-4cb7-bfcd-ee2522c8f633";
+
+    #[test]
+    fn bip39_vectors_work() {
+        // Test vectors from the BIP39 specification.
+        let id = ShortGuid::from_bytes([0; 16]);
+        let words = "abandon abandon abandon abandon abandon abandon \
+                     abandon abandon abandon abandon abandon about";
+        assert_eq!(id.to_mnemonic(), words);
+        assert_eq!(ShortGuid::parse_mnemonic(words).unwrap(), id);
+
+        let id = ShortGuid::from_bytes([0x7f; 16]);
+        let words = "legal winner thank year wave sausage worth useful legal winner thank yellow";
+        assert_eq!(id.to_mnemonic(), words);
+        assert_eq!(ShortGuid::parse_mnemonic(words).unwrap(), id);
+    }
+
+    #[test]
+    fn roundtrip_works() {
+        for value in [
+            "00000000-0000-0000-0000-000000000000",
+            "ffffffff-ffff-ffff-ffff-ffffffffffff",
+            UUID,
+        ] {
+            let id = ShortGuid::try_parse(value).unwrap();
+            assert_eq!(ShortGuid::parse_mnemonic(id.to_mnemonic()).unwrap(), id);
+        }
+    }
+
+    #[test]
+    fn tolerant_parsing_works() {
+        let id = ShortGuid::from_bytes([0x7f; 16]);
+        for words in [
+            "  LEGAL Winner\tthank\n year-wave, sausage worth useful legal winner thank yellow ",
+            "lega winn than year wave saus wort usef lega winn than yell",
+            "legall winner thnak year wave sausag wurth useful legal winner thank yello",
+        ] {
+            assert_eq!(ShortGuid::parse_mnemonic(words).unwrap(), id, "{words}");
+        }
+    }
+
+    #[test]
+    fn corrections_are_reported() {
+        let id = ShortGuid::from_bytes([0x7f; 16]);
+        let words = "legal winner thank year wave sausage worth useful legal winner thank yellow";
+        assert_eq!(
+            ShortGuid::parse_mnemonic_with_corrections(words).unwrap(),
+            (id, vec![])
+        );
+
+        let words = "lega winn than year wave saus wort usef lega winn than yell";
+        assert_eq!(
+            ShortGuid::parse_mnemonic_with_corrections(words).unwrap(),
+            (id, vec![])
+        );
+
+        let words = "legall winner thnak year wave sausag wurth useful legal winner thank yello";
+        assert_eq!(
+            ShortGuid::parse_mnemonic_with_corrections(words).unwrap(),
+            (id, vec![0, 2, 6])
+        );
+    }
+
+    #[test]
+    fn ambiguous_corrections_fail() {
+        // "cst" is one edit away from both "cat" and "cost".
+        assert!(Language::English.find_word("cst").is_none());
+        assert_eq!(
+            ShortGuid::parse_mnemonic(
+                "legal winner thank year wave sausage worth useful legal winner cst yellow"
+            )
+            .unwrap_err(),
+            ParseError::InvalidMnemonic(MnemonicError::AmbiguousWord(10))
+        );
+    }
+
+    #[test]
+    fn invalid_input_fails() {
+        assert_eq!(
+            ShortGuid::parse_mnemonic("legal winner thank").unwrap_err(),
+            ParseError::InvalidMnemonic(MnemonicError::InvalidWordCount(3))
+        );
+        assert_eq!(
+            ShortGuid::parse_mnemonic(
+                "legal winner thank year wave sausage worth useful legal winner xyzzy yellow"
+            )
+            .unwrap_err(),
+            ParseError::InvalidMnemonic(MnemonicError::UnknownWord(10))
+        );
+        assert_eq!(
+            ShortGuid::parse_mnemonic(
+                "legal winner thank year wave sausage worth useful legal winner thank zoo"
+            )
+            .unwrap_err(),
+            ParseError::InvalidMnemonic(MnemonicError::InvalidChecksum)
+        );
+    }
+
+    #[test]
+    fn within_one_edit_works() {
+        assert!(within_one_edit("winner", "winner"));
+        assert!(within_one_edit("winer", "winner"));
+        assert!(within_one_edit("winnner", "winner"));
+        assert!(within_one_edit("wimner", "winner"));
+        assert!(within_one_edit("wniner", "winner"));
+        assert!(!within_one_edit("wimer", "winner"));
+        assert!(!within_one_edit("wnienr", "winner"));
+        assert!(!within_one_edit("winnerss", "winner"));
+    }
+}
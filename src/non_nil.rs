@@ -0,0 +1,248 @@
+use crate::{ParseError, ShortGuid};
+use std::fmt::{Debug, Display, Formatter};
+use std::num::NonZeroU128;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// A [`ShortGuid`] that cannot hold the nil (all zeros) value.
+///
+/// The value is stored as a [`NonZeroU128`], which allows the compiler to use the nil
+/// value as a niche: `Option<NonNilShortGuid>` has the same size as `NonNilShortGuid`.
+///
+/// Unlike [`ShortGuid`], which is aligned like its 16 bytes, this type has the alignment
+/// of a `u128`, so it cannot be reinterpreted as or borrowed from a [`ShortGuid`]. Convert
+/// by value using [`NonNilShortGuid::get`] and [`NonNilShortGuid::new`] instead.
+///
+/// ## Example
+///
+/// ```
+/// # use shortguid::{NonNilShortGuid, ParseError, ShortGuid};
+/// let id = NonNilShortGuid::try_parse("yaZG05xhTLe_ze4lIsj2Mw").unwrap();
+/// assert_eq!(id.get(), ShortGuid::try_parse("yaZG05xhTLe_ze4lIsj2Mw").unwrap());
+///
+/// assert_eq!(
+///     NonNilShortGuid::try_parse("AAAAAAAAAAAAAAAAAAAAAA").unwrap_err(),
+///     ParseError::NilValue
+/// );
+/// assert_eq!(std::mem::size_of::<Option<NonNilShortGuid>>(), 16);
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[repr(transparent)]
+pub struct NonNilShortGuid(NonZeroU128);
+
+// Only the size matches `ShortGuid`; the alignment is that of `u128`, see above.
+const _: () = {
+    assert!(std::mem::size_of::<NonNilShortGuid>() == std::mem::size_of::<ShortGuid>());
+    assert!(std::mem::size_of::<Option<NonNilShortGuid>>() == std::mem::size_of::<ShortGuid>());
+    assert!(std::mem::align_of::<NonNilShortGuid>() == std::mem::align_of::<u128>());
+};
+
+impl NonNilShortGuid {
+    /// Generates a new [`NonNilShortGuid`] based on a random UUID v4.
+    #[cfg_attr(docsrs, doc(cfg(feature = "random")))]
+    #[cfg(feature = "random")]
+    #[inline(always)]
+    pub fn new_random() -> Self {
+        // A UUID v4 always has its version bits set and is never nil.
+        Self::new(ShortGuid::new_random()).expect("UUID v4 is never nil")
+    }
+
+    /// Creates a new [`NonNilShortGuid`] if the provided [`ShortGuid`] is not nil.
+    #[inline]
+    pub const fn new(id: ShortGuid) -> Option<Self> {
+        Self::from_uuid(*id.as_uuid())
+    }
+
+    /// Creates a new [`NonNilShortGuid`] without checking whether the value is nil.
+    ///
+    /// ## Safety
+    /// The provided [`ShortGuid`] must not be nil.
+    #[inline]
+    pub const unsafe fn new_unchecked(id: ShortGuid) -> Self {
+        Self(NonZeroU128::new_unchecked(id.as_uuid().as_u128()))
+    }
+
+    /// Creates a new [`NonNilShortGuid`] if the provided [`Uuid`] is not nil.
+    #[inline]
+    pub const fn from_uuid(uuid: Uuid) -> Option<Self> {
+        match NonZeroU128::new(uuid.as_u128()) {
+            Some(value) => Some(Self(value)),
+            None => None,
+        }
+    }
+
+    /// Creates a new [`NonNilShortGuid`] if the provided bytes are not all zeros.
+    #[inline]
+    pub const fn from_bytes(bytes: [u8; 16]) -> Option<Self> {
+        Self::from_uuid(Uuid::from_bytes(bytes))
+    }
+
+    /// Tries to parse the value like [`ShortGuid::try_parse`], rejecting the nil value.
+    pub fn try_parse<S: AsRef<str>>(value: S) -> Result<Self, ParseError> {
+        let id = ShortGuid::try_parse(value)?;
+        Self::try_from(id)
+    }
+
+    /// Returns the value as a [`ShortGuid`].
+    #[inline]
+    pub const fn get(self) -> ShortGuid {
+        ShortGuid::new_from_uuid(self.to_uuid())
+    }
+
+    /// Returns the value as a [`Uuid`].
+    #[inline]
+    pub const fn to_uuid(self) -> Uuid {
+        Uuid::from_u128(self.0.get())
+    }
+
+    /// Returns the 16 octets of the value.
+    #[inline]
+    pub const fn to_bytes(self) -> [u8; 16] {
+        self.0.get().to_be_bytes()
+    }
+}
+
+impl Debug for NonNilShortGuid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&self.get(), f)
+    }
+}
+
+impl Display for NonNilShortGuid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.get(), f)
+    }
+}
+
+impl TryFrom<ShortGuid> for NonNilShortGuid {
+    type Error = ParseError;
+
+    fn try_from(value: ShortGuid) -> Result<Self, Self::Error> {
+        Self::new(value).ok_or(ParseError::NilValue)
+    }
+}
+
+impl TryFrom<Uuid> for NonNilShortGuid {
+    type Error = ParseError;
+
+    fn try_from(value: Uuid) -> Result<Self, Self::Error> {
+        Self::from_uuid(value).ok_or(ParseError::NilValue)
+    }
+}
+
+impl TryFrom<[u8; 16]> for NonNilShortGuid {
+    type Error = ParseError;
+
+    fn try_from(value: [u8; 16]) -> Result<Self, Self::Error> {
+        Self::from_bytes(value).ok_or(ParseError::NilValue)
+    }
+}
+
+impl TryFrom<String> for NonNilShortGuid {
+    type Error = ParseError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::try_parse(value)
+    }
+}
+
+impl TryFrom<&str> for NonNilShortGuid {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::try_parse(value)
+    }
+}
+
+impl FromStr for NonNilShortGuid {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_parse(s)
+    }
+}
+
+impl From<NonNilShortGuid> for ShortGuid {
+    fn from(value: NonNilShortGuid) -> Self {
+        value.get()
+    }
+}
+
+impl From<NonNilShortGuid> for Uuid {
+    fn from(value: NonNilShortGuid) -> Self {
+        value.to_uuid()
+    }
+}
+
+impl PartialEq<ShortGuid> for NonNilShortGuid {
+    fn eq(&self, other: &ShortGuid) -> bool {
+        self.get().eq(other)
+    }
+}
+
+impl PartialEq<Uuid> for NonNilShortGuid {
+    fn eq(&self, other: &Uuid) -> bool {
+        self.to_uuid().eq(other)
+    }
+}
+
+impl PartialEq<str> for NonNilShortGuid {
+    fn eq(&self, other: &str) -> bool {
+        self.get().eq(other)
+    }
+}
+
+impl PartialEq<&str> for NonNilShortGuid {
+    fn eq(&self, other: &&str) -> bool {
+        self.get().eq(*other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn niche_is_used() {
+        assert_eq!(std::mem::size_of::<NonNilShortGuid>(), 16);
+        assert_eq!(std::mem::size_of::<Option<NonNilShortGuid>>(), 16);
+    }
+
+    #[test]
+    fn nil_is_rejected() {
+        assert!(NonNilShortGuid::new(ShortGuid::default()).is_none());
+        assert!(NonNilShortGuid::from_uuid(Uuid::nil()).is_none());
+        assert!(NonNilShortGuid::from_bytes([0; 16]).is_none());
+        assert_eq!(
+            NonNilShortGuid::try_from(ShortGuid::default()).unwrap_err(),
+            ParseError::NilValue
+        );
+        assert_eq!(
+            NonNilShortGuid::try_parse("00000000-0000-0000-0000-000000000000").unwrap_err(),
+            ParseError::NilValue
+        );
+    }
+
+    #[test]
+    fn roundtrip_works() {
+        let id = ShortGuid::try_parse("a1a2a3a4-b1b2-c1c2-d1d2-d3d4d5d6d7d8").unwrap();
+        let non_nil = NonNilShortGuid::new(id).unwrap();
+        assert_eq!(non_nil.get(), id);
+        assert_eq!(&non_nil.to_bytes(), id.as_bytes());
+        assert_eq!(non_nil.to_string(), id.to_string());
+        assert_eq!(format!("{non_nil:?}"), format!("{id:?}"));
+    }
+
+    #[test]
+    fn ordering_matches_short_guid() {
+        let a = ShortGuid::try_parse("00000000-0000-0000-0000-0000000000ff").unwrap();
+        let b = ShortGuid::try_parse("01000000-0000-0000-0000-000000000000").unwrap();
+        assert!(a < b);
+        assert!(NonNilShortGuid::new(a).unwrap() < NonNilShortGuid::new(b).unwrap());
+    }
+
+    #[test]
+    fn new_random_works() {
+        assert_ne!(NonNilShortGuid::new_random(), NonNilShortGuid::new_random());
+    }
+}
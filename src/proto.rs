@@ -0,0 +1,174 @@
+//! Protocol Buffers support using `prost`.
+//!
+//! The [`ShortGuid`] message carries an ID as its 16 raw bytes and
+//! matches the `shortguid.ShortGuid` message declared in `proto/shortguid.proto`:
+//!
+//! ```protobuf
+//! syntax = "proto3";
+//!
+//! package shortguid;
+//!
+//! message ShortGuid {
+//!   bytes value = 1;
+//! }
+//! ```
+//!
+//! To use the message from your own `.proto` files, import the file and map the type to
+//! this module in `prost-build`, e.g. using
+//! `Config::extern_path(".shortguid.ShortGuid", "::shortguid::proto::ShortGuid")`.
+//!
+//! IDs in plain `bytes` fields can be converted using `TryFrom` as well. For `string` fields,
+//! use [`parse_string`], which rejects the empty string that proto3 decodes for unset fields.
+//!
+//! ```
+//! use prost::Message;
+//! use shortguid::{proto, ShortGuid};
+//!
+//! let id = ShortGuid::try_parse("yaZG05xhTLe_ze4lIsj2Mw").unwrap();
+//! let encoded = proto::ShortGuid::from(id).encode_to_vec();
+//! assert_eq!(encoded.len(), 18);
+//!
+//! let decoded = proto::ShortGuid::decode(encoded.as_slice()).unwrap();
+//! assert_eq!(ShortGuid::try_from(decoded).unwrap(), id);
+//!
+//! // IDs in `bytes` and `string` fields.
+//! let bytes = id.as_bytes().to_vec();
+//! assert_eq!(ShortGuid::try_from(bytes).unwrap(), id);
+//! assert_eq!(proto::parse_string(&id.to_string()).unwrap(), id);
+//! assert!(proto::parse_string("").is_err());
+//! ```
+
+use crate::ParseError;
+use bytes::Bytes;
+
+/// Parses an ID from a protobuf `string` field holding a [`ShortGuid`](crate::ShortGuid)
+/// or any UUID string.
+///
+/// Unlike [`ShortGuid::try_parse`](crate::ShortGuid::try_parse), the empty string is rejected:
+/// proto3 decodes unset `string` fields as `""`, which would otherwise become the nil ID.
+pub fn parse_string(value: &str) -> Result<crate::ShortGuid, ParseError> {
+    crate::ShortGuid::try_parse_non_empty(value)
+}
+
+/// The `shortguid.ShortGuid` Protocol Buffers message.
+#[derive(Clone, PartialEq, Eq, Hash, prost::Message)]
+pub struct ShortGuid {
+    /// The 16 bytes of the ID.
+    #[prost(bytes = "vec", tag = "1")]
+    pub value: Vec<u8>,
+}
+
+impl From<crate::ShortGuid> for ShortGuid {
+    fn from(value: crate::ShortGuid) -> Self {
+        Self {
+            value: value.as_bytes().to_vec(),
+        }
+    }
+}
+
+impl TryFrom<ShortGuid> for crate::ShortGuid {
+    type Error = ParseError;
+
+    fn try_from(value: ShortGuid) -> Result<Self, Self::Error> {
+        Self::try_from(&value)
+    }
+}
+
+impl TryFrom<&ShortGuid> for crate::ShortGuid {
+    type Error = ParseError;
+
+    fn try_from(value: &ShortGuid) -> Result<Self, Self::Error> {
+        Self::from_slice(&value.value)
+    }
+}
+
+impl From<crate::ShortGuid> for Bytes {
+    fn from(value: crate::ShortGuid) -> Self {
+        Bytes::copy_from_slice(value.as_bytes())
+    }
+}
+
+impl TryFrom<Bytes> for crate::ShortGuid {
+    type Error = ParseError;
+
+    fn try_from(value: Bytes) -> Result<Self, Self::Error> {
+        Self::from_slice(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost::Message;
+
+    const UUID: &str = "c9a646d3-9c61-4cb7-bfcd-ee2522c8f633";
+
+    /// A message embedding the ID in the ways commonly found in gRPC APIs.
+    #[derive(Clone, PartialEq, prost::Message)]
+    struct Order {
+        #[prost(message, optional, tag = "1")]
+        id: Option<ShortGuid>,
+        #[prost(bytes = "bytes", tag = "2")]
+        customer_id: Bytes,
+        #[prost(string, tag = "3")]
+        parent_id: String,
+    }
+
+    #[test]
+    fn encode_works() {
+        let id = crate::ShortGuid::try_parse(UUID).unwrap();
+        let encoded = ShortGuid::from(id).encode_to_vec();
+
+        // Field 1, length-delimited, 16 bytes.
+        let mut expected = vec![0x0a, 0x10];
+        expected.extend_from_slice(id.as_bytes());
+        assert_eq!(encoded, expected);
+    }
+
+    #[test]
+    fn decode_works() {
+        let id = crate::ShortGuid::try_parse(UUID).unwrap();
+        let order = Order {
+            id: Some(id.into()),
+            customer_id: id.into(),
+            parent_id: id.to_string(),
+        };
+
+        let encoded = order.encode_to_vec();
+        let decoded = Order::decode(encoded.as_slice()).unwrap();
+        assert_eq!(decoded, order);
+
+        assert_eq!(crate::ShortGuid::try_from(decoded.id.unwrap()).unwrap(), id);
+        assert_eq!(crate::ShortGuid::try_from(decoded.customer_id).unwrap(), id);
+        assert_eq!(parse_string(&decoded.parent_id).unwrap(), id);
+        assert_eq!(parse_string(UUID).unwrap(), id);
+    }
+
+    #[test]
+    fn unset_fields_fail() {
+        let order = Order::decode(Order::default().encode_to_vec().as_slice()).unwrap();
+        assert_eq!(order.id, None);
+        assert!(matches!(
+            crate::ShortGuid::try_from(order.customer_id),
+            Err(ParseError::InvalidSlice(_))
+        ));
+        assert_eq!(
+            parse_string(&order.parent_id).unwrap_err(),
+            ParseError::InvalidLength(0)
+        );
+    }
+
+    #[test]
+    fn invalid_values_fail() {
+        // An empty message decodes to an empty value.
+        let message = ShortGuid::decode(&[][..]).unwrap();
+        assert!(matches!(
+            crate::ShortGuid::try_from(&message),
+            Err(ParseError::InvalidSlice(_))
+        ));
+
+        assert!(crate::ShortGuid::try_from(vec![0u8; 15]).is_err());
+        assert!(crate::ShortGuid::try_from(Bytes::from_static(&[0; 17])).is_err());
+        assert!(parse_string("not an id").is_err());
+    }
+}
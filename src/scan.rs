@@ -0,0 +1,322 @@
+//! Finding and replacing IDs inside free text such as log lines, URLs and error messages.
+//!
+//! A token is a maximal run of the characters `A-Z`, `a-z`, `0-9`, `-` and `_`, i.e. the
+//! characters that may appear in a hyphenated UUID or a [`ShortGuid`]. A token matches
+//! if it is a 36 character hyphenated UUID or a 22 character [`ShortGuid`] that decodes to
+//! exactly 16 bytes. IDs embedded in longer tokens, such as `user_yaZG05xhTLe_ze4lIsj2Mw`,
+//! are therefore not matched.
+//!
+//! Note that any 22 character word of the Base64 alphabet ending in `A`, `Q`, `g` or `w`
+//! is a valid [`ShortGuid`].
+//!
+//! ```
+//! use shortguid::{scan, Format};
+//!
+//! let line = "GET /orders/yaZG05xhTLe_ze4lIsj2Mw failed (request a1a2a3a4-b1b2-c1c2-d1d2-d3d4d5d6d7d8)";
+//! let ids: Vec<_> = scan::find_iter(line).map(|m| m.as_str()).collect();
+//! assert_eq!(ids, ["yaZG05xhTLe_ze4lIsj2Mw", "a1a2a3a4-b1b2-c1c2-d1d2-d3d4d5d6d7d8"]);
+//!
+//! assert_eq!(
+//!     scan::replace_all(line, Format::Short),
+//!     "GET /orders/yaZG05xhTLe_ze4lIsj2Mw failed (request oaKjpLGywcLR0tPU1dbX2A)"
+//! );
+//! ```
+
+use crate::{Format, ShortGuid};
+use std::borrow::Cow;
+use std::iter::FusedIterator;
+use std::ops::Range;
+use uuid::Uuid;
+
+/// A regular expression matching a hyphenated UUID, without word boundaries.
+pub const UUID_PATTERN: &str =
+    "[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}";
+
+/// A regular expression matching a 22 character [`ShortGuid`], without word boundaries.
+///
+/// The last character only carries two bits of the ID, which restricts it to `A`, `Q`, `g` or `w`.
+pub const SHORT_GUID_PATTERN: &str = "[A-Za-z0-9_-]{21}[AQgw]";
+
+/// A regular expression matching the characters that delimit a token.
+///
+/// Use this to express the word boundaries applied by [`find_iter`], e.g. as
+/// `(?:^|[^A-Za-z0-9_-])(<pattern>)(?:$|[^A-Za-z0-9_-])`.
+pub const BOUNDARY_PATTERN: &str = "[^A-Za-z0-9_-]";
+
+/// The length of a hyphenated UUID.
+const UUID_LEN: usize = 36;
+
+/// The length of a [`ShortGuid`].
+const SHORT_GUID_LEN: usize = 22;
+
+/// An ID found in a haystack.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Match<'h> {
+    haystack: &'h str,
+    range: Range<usize>,
+    format: Format,
+    id: ShortGuid,
+}
+
+impl<'h> Match<'h> {
+    /// Returns the byte offset of the start of the match.
+    #[inline]
+    pub fn start(&self) -> usize {
+        self.range.start
+    }
+
+    /// Returns the byte offset directly after the end of the match.
+    #[inline]
+    pub fn end(&self) -> usize {
+        self.range.end
+    }
+
+    /// Returns the byte range of the match.
+    #[inline]
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
+    /// Returns the matched text.
+    #[inline]
+    pub fn as_str(&self) -> &'h str {
+        &self.haystack[self.range()]
+    }
+
+    /// Returns the format of the matched text, i.e. [`Format::Uuid`] or [`Format::Short`].
+    #[inline]
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Returns the matched ID.
+    #[inline]
+    pub fn id(&self) -> ShortGuid {
+        self.id
+    }
+}
+
+/// An iterator over all IDs in a haystack; see [`find_iter`].
+#[derive(Clone, Debug)]
+pub struct Matches<'h> {
+    haystack: &'h str,
+    position: usize,
+}
+
+impl<'h> Iterator for Matches<'h> {
+    type Item = Match<'h>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.haystack.as_bytes();
+        while self.position < bytes.len() {
+            let start = self.position
+                + bytes[self.position..]
+                    .iter()
+                    .position(|&b| is_token_byte(b))
+                    .unwrap_or(bytes.len() - self.position);
+            let end = start
+                + bytes[start..]
+                    .iter()
+                    .position(|&b| !is_token_byte(b))
+                    .unwrap_or(bytes.len() - start);
+            self.position = end;
+
+            let token = &self.haystack[start..end];
+            let found = match token.len() {
+                UUID_LEN => Uuid::try_parse(token)
+                    .ok()
+                    .map(|uuid| (Format::Uuid, ShortGuid::from(uuid))),
+                SHORT_GUID_LEN => ShortGuid::try_decode(token)
+                    .ok()
+                    .map(|uuid| (Format::Short, ShortGuid::from(uuid))),
+                _ => None,
+            };
+
+            if let Some((format, id)) = found {
+                return Some(Match {
+                    haystack: self.haystack,
+                    range: start..end,
+                    format,
+                    id,
+                });
+            }
+        }
+        None
+    }
+}
+
+impl FusedIterator for Matches<'_> {}
+
+/// Returns an iterator over all hyphenated UUIDs and [`ShortGuid`]s in the haystack.
+pub fn find_iter(haystack: &str) -> Matches<'_> {
+    Matches {
+        haystack,
+        position: 0,
+    }
+}
+
+/// Replaces every ID in the haystack with its representation in the given format.
+///
+/// Returns the haystack unchanged if it contains no IDs.
+pub fn replace_all(haystack: &str, format: Format) -> Cow<'_, str> {
+    replace_all_with(haystack, |m| format.encode(&m.id()))
+}
+
+/// Replaces every ID in the haystack with the value returned by the closure.
+///
+/// Returns the haystack unchanged if it contains no IDs.
+///
+/// ```
+/// use shortguid::scan;
+///
+/// let line = "order yaZG05xhTLe_ze4lIsj2Mw shipped";
+/// let redacted = scan::replace_all_with(line, |m| "*".repeat(m.as_str().len()));
+/// assert_eq!(redacted, "order ********************** shipped");
+/// ```
+pub fn replace_all_with<'h, F>(haystack: &'h str, mut replacement: F) -> Cow<'h, str>
+where
+    F: FnMut(&Match<'h>) -> String,
+{
+    let mut matches = find_iter(haystack).peekable();
+    if matches.peek().is_none() {
+        return Cow::Borrowed(haystack);
+    }
+
+    let mut result = String::with_capacity(haystack.len());
+    let mut last = 0;
+    for m in matches {
+        result.push_str(&haystack[last..m.start()]);
+        result.push_str(&replacement(&m));
+        last = m.end();
+    }
+    result.push_str(&haystack[last..]);
+    Cow::Owned(result)
+}
+
+/// Tests whether the byte may be part of a token.
+#[inline]
+fn is_token_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'-' || b == b'_'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use regex::Regex;
+
+    const UUID: &str = "c9a646d3-9c61-4cb7-bfcd-ee2522c8f633";
+    const SHORT: &str = "yaZG05xhTLe_ze4lIsj2Mw";
+
+    #[test]
+    fn find_iter_works() {
+        let haystack = format!("id={UUID}, url=https://example.com/{SHORT}?x=1 ({SHORT})");
+        let matches: Vec<_> = find_iter(&haystack).collect();
+        assert_eq!(matches.len(), 3);
+
+        let id = ShortGuid::try_parse(UUID).unwrap();
+        assert!(matches.iter().all(|m| m.id() == id));
+        assert_eq!(matches[0].range(), 3..39);
+        assert_eq!(matches[0].format(), Format::Uuid);
+        assert_eq!(matches[1].as_str(), SHORT);
+        assert_eq!(matches[1].format(), Format::Short);
+        assert_eq!(&haystack[matches[2].range()], SHORT);
+    }
+
+    #[test]
+    fn word_boundaries_are_respected() {
+        for haystack in [
+            format!("user_{SHORT}"),
+            format!("{SHORT}x"),
+            format!("{UUID}-1"),
+            format!("x{UUID}"),
+            SHORT[1..].to_string(),
+            "c9a646d39c614cb7bfcdee2522c8f633".to_string(),
+        ] {
+            assert_eq!(find_iter(&haystack).count(), 0, "{haystack}");
+        }
+
+        let haystack = format!("ä{SHORT}ö\n{{{UUID}}}");
+        let matches: Vec<_> = find_iter(&haystack).map(|m| m.as_str()).collect();
+        assert_eq!(matches, [SHORT, UUID]);
+    }
+
+    #[test]
+    fn invalid_tokens_are_skipped() {
+        // 22 characters, but with trailing bits set.
+        assert_eq!(find_iter("yaZG05xhTLe_ze4lIsj2Mx").count(), 0);
+        // 36 characters, but not a UUID.
+        assert_eq!(find_iter("c9a646d3-9c61-4cb7-bfcd-ee2522c8f63g").count(), 0);
+        assert_eq!(find_iter("c9a646d3-9c61-4cb7-bfcd-ee2522c8f6-3").count(), 0);
+    }
+
+    #[test]
+    fn replace_all_works() {
+        let haystack = format!("{UUID} and {SHORT}.");
+        assert_eq!(
+            replace_all(&haystack, Format::Short),
+            format!("{SHORT} and {SHORT}.")
+        );
+        assert_eq!(
+            replace_all(&haystack, Format::Uuid),
+            format!("{UUID} and {UUID}.")
+        );
+        assert!(matches!(
+            replace_all("nothing to see", Format::Uuid),
+            Cow::Borrowed("nothing to see")
+        ));
+    }
+
+    #[test]
+    fn replace_all_with_works() {
+        let haystack = format!("[{UUID}] [{SHORT}]");
+        let replaced = replace_all_with(&haystack, |m| format!("{:?}", m.format()));
+        assert_eq!(replaced, "[Uuid] [Short]");
+    }
+
+    #[test]
+    fn patterns_agree_with_scanner() {
+        let uuid = Regex::new(&format!("^(?:{UUID_PATTERN})$")).unwrap();
+        let short = Regex::new(&format!("^(?:{SHORT_GUID_PATTERN})$")).unwrap();
+
+        for candidate in [
+            UUID,
+            SHORT,
+            "AAAAAAAAAAAAAAAAAAAAAA",
+            "yaZG05xhTLe_ze4lIsj2Mx",
+            "c9a646d3-9c61-4cb7-bfcd-ee2522c8f63g",
+            "C9A646D3-9C61-4CB7-BFCD-EE2522C8F633",
+        ] {
+            let expected = find_iter(candidate).next().map(|m| m.format());
+            let actual = if uuid.is_match(candidate) {
+                Some(Format::Uuid)
+            } else if short.is_match(candidate) {
+                Some(Format::Short)
+            } else {
+                None
+            };
+            assert_eq!(actual, expected, "{candidate}");
+        }
+
+        for _ in 0..100 {
+            let id = ShortGuid::new_random();
+            assert!(short.is_match(&id.to_string()));
+            assert!(uuid.is_match(&id.as_uuid().hyphenated().to_string()));
+        }
+    }
+
+    #[test]
+    fn boundary_pattern_agrees_with_scanner() {
+        let pattern = Regex::new(&format!(
+            "(?:^|{BOUNDARY_PATTERN})({UUID_PATTERN}|{SHORT_GUID_PATTERN})(?:$|{BOUNDARY_PATTERN})"
+        ))
+        .unwrap();
+
+        let haystack = format!("user_{SHORT} /{SHORT}/ {UUID}-1 ({UUID})");
+        let expected: Vec<_> = find_iter(&haystack).map(|m| m.range()).collect();
+        let actual: Vec<_> = pattern
+            .captures_iter(&haystack)
+            .map(|c| c.get(1).unwrap().range())
+            .collect();
+        assert_eq!(actual, expected);
+    }
+}
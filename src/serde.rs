@@ -7,10 +7,23 @@
 //
 // SPDX-License-Identifier: EUPL-1.2 or MIT or Apache-2.0
 
-use crate::{ParseError, ShortGuid};
-use std::fmt::Formatter;
+//! Serialization and deserialization support using `serde`.
+//!
+//! [`ShortGuid`] implements [`Serialize`](serde::Serialize) and
+//! [`Deserialize`](serde::Deserialize) directly. The modules in here can be used with
+//! `#[serde(with = "...")]` to change the representation of individual fields.
+
+use crate::typed::{fmt_prefixed, parse_prefixed};
+use crate::{
+    IdPrefix, NonNilShortGuid, ParseError, ShortGuid, TypeId, TypedShortGuid, VersionedShortGuid,
+};
+use std::fmt::{Display, Formatter};
 use uuid::Uuid;
 
+fn de_error<E: serde::de::Error>(e: ParseError) -> E {
+    E::custom(format_args!("ShortGuid parsing failed: {}", e))
+}
+
 #[cfg(feature = "serde")]
 impl serde::Serialize for ShortGuid {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -18,7 +31,7 @@ impl serde::Serialize for ShortGuid {
         S: serde::Serializer,
     {
         if serializer.is_human_readable() {
-            serializer.serialize_str(Self::encode(self.0).as_str())
+            serializer.serialize_str(Self::encode(self.as_uuid()).as_str())
         } else {
             serializer.serialize_bytes(self.as_bytes())
         }
@@ -31,10 +44,6 @@ impl<'de> serde::Deserialize<'de> for ShortGuid {
     where
         D: serde::Deserializer<'de>,
     {
-        fn de_error<E: serde::de::Error>(e: ParseError) -> E {
-            E::custom(format_args!("ShortGuid parsing failed: {}", e))
-        }
-
         if deserializer.is_human_readable() {
             struct ShortGuidVisitor;
 
@@ -78,7 +87,7 @@ impl<'de> serde::Deserialize<'de> for ShortGuid {
                         match seq.next_element()? { Some(e) => e, None => return Err(Error::invalid_length(16, &self)) },
                     ];
 
-                    Ok(ShortGuid::from_bytes(&bytes))
+                    Ok(ShortGuid::from_bytes(bytes))
                 }
             }
 
@@ -89,3 +98,1014 @@ impl<'de> serde::Deserialize<'de> for ShortGuid {
         }
     }
 }
+
+#[cfg(feature = "serde")]
+impl<T: IdPrefix> serde::Serialize for TypedShortGuid<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serialize_prefixed(self.as_short_guid(), T::PREFIX, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: IdPrefix> serde::Deserialize<'de> for TypedShortGuid<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserialize_prefixed(deserializer, T::PREFIX).map(TypedShortGuid::new)
+    }
+}
+
+/// Serializes `id` as `{prefix}_{id}` for human-readable formats, and as a plain
+/// [`ShortGuid`] otherwise.
+#[doc(hidden)]
+pub fn serialize_prefixed<S>(id: &ShortGuid, prefix: &str, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    use serde::Serialize;
+
+    struct Prefixed<'a>(&'a ShortGuid, &'a str);
+
+    impl Display for Prefixed<'_> {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            fmt_prefixed(f, self.1, self.0)
+        }
+    }
+
+    if serializer.is_human_readable() {
+        serializer.collect_str(&Prefixed(id, prefix))
+    } else {
+        id.serialize(serializer)
+    }
+}
+
+/// Deserializes a value written by [`serialize_prefixed`], rejecting any other prefix.
+#[doc(hidden)]
+pub fn deserialize_prefixed<'de, D>(
+    deserializer: D,
+    prefix: &'static str,
+) -> Result<ShortGuid, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::Deserialize;
+
+    if deserializer.is_human_readable() {
+        struct PrefixedVisitor(&'static str);
+
+        impl<'vi> serde::de::Visitor<'vi> for PrefixedVisitor {
+            type Value = ShortGuid;
+
+            fn expecting(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+                write!(
+                    formatter,
+                    "a ShortGuid string prefixed with \"{}_\"",
+                    self.0
+                )
+            }
+
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<ShortGuid, E> {
+                parse_prefixed(value, self.0).map_err(de_error)
+            }
+        }
+
+        deserializer.deserialize_str(PrefixedVisitor(prefix))
+    } else {
+        ShortGuid::deserialize(deserializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for TypeId {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for TypeId {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct TypeIdVisitor;
+
+        impl<'vi> serde::de::Visitor<'vi> for TypeIdVisitor {
+            type Value = TypeId;
+
+            fn expecting(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+                write!(formatter, "a TypeID string")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<TypeId, E> {
+                TypeId::try_parse(value).map_err(de_error)
+            }
+        }
+
+        deserializer.deserialize_str(TypeIdVisitor)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const VERSION: usize> serde::Serialize for VersionedShortGuid<VERSION> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.as_short_guid().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const VERSION: usize> serde::Deserialize<'de> for VersionedShortGuid<VERSION> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let id = ShortGuid::deserialize(deserializer)?;
+        VersionedShortGuid::new(id).map_err(de_error)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for NonNilShortGuid {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.get().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for NonNilShortGuid {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let id = ShortGuid::deserialize(deserializer)?;
+        NonNilShortGuid::try_from(id).map_err(de_error)
+    }
+}
+
+/// Generates the `option`, `vec` and `map` submodules of a `with` module, which apply the
+/// module's `serialize` and `deserialize` functions to `Option<ShortGuid>`, `Vec<ShortGuid>`
+/// and the keys of a map, respectively.
+///
+/// `with_collections!(without_map)` omits the `map` submodule for representations that
+/// can never be used as map keys.
+macro_rules! with_collections {
+    () => {
+        with_collections!(without_map);
+
+        /// The parent module's representation for the keys of a map, such as a
+        /// `HashMap<ShortGuid, V>` or `BTreeMap<ShortGuid, V>`.
+        ///
+        /// The format must support the representation as a map key. For example, JSON
+        /// only supports string and integer keys, so byte representations require a
+        /// format such as bincode, postcard or CBOR.
+        pub mod map {
+            use super::{De, Ser};
+            use serde::de::{MapAccess, Visitor};
+            use serde::{Deserialize, Deserializer, Serialize, Serializer};
+            use std::fmt::Formatter;
+            use std::marker::PhantomData;
+            use $crate::ShortGuid;
+
+            /// Serializes the map with the keys in the parent module's representation.
+            pub fn serialize<'a, M, V, S>(map: &'a M, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                &'a M: IntoIterator<Item = (&'a ShortGuid, &'a V)>,
+                V: Serialize + 'a,
+                S: Serializer,
+            {
+                serializer.collect_map(map.into_iter().map(|(id, value)| (Ser(id), value)))
+            }
+
+            /// Deserializes a map with the keys in the parent module's representation.
+            pub fn deserialize<'de, M, V, D>(deserializer: D) -> Result<M, D::Error>
+            where
+                M: FromIterator<(ShortGuid, V)>,
+                V: Deserialize<'de>,
+                D: Deserializer<'de>,
+            {
+                struct MapVisitor<M, V>(PhantomData<fn() -> (M, V)>);
+
+                impl<'vi, M, V> Visitor<'vi> for MapVisitor<M, V>
+                where
+                    M: FromIterator<(ShortGuid, V)>,
+                    V: Deserialize<'vi>,
+                {
+                    type Value = M;
+
+                    fn expecting(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "a map with ShortGuid keys")
+                    }
+
+                    fn visit_map<A>(self, mut map: A) -> Result<M, A::Error>
+                    where
+                        A: MapAccess<'vi>,
+                    {
+                        std::iter::from_fn(|| map.next_entry::<De, V>().transpose())
+                            .map(|entry| entry.map(|(De(id), value)| (id, value)))
+                            .collect()
+                    }
+                }
+
+                deserializer.deserialize_map(MapVisitor(PhantomData))
+            }
+        }
+    };
+    (without_map) => {
+        /// Applies the parent module's representation by reference.
+        struct Ser<'a>(&'a $crate::ShortGuid);
+
+        impl serde::Serialize for Ser<'_> {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                serialize(self.0, serializer)
+            }
+        }
+
+        /// Applies the parent module's representation to an owned value.
+        struct De($crate::ShortGuid);
+
+        impl<'de> serde::Deserialize<'de> for De {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                deserialize(deserializer).map(De)
+            }
+        }
+
+        /// The parent module's representation for `Option<ShortGuid>`.
+        pub mod option {
+            use super::{De, Ser};
+            use serde::{Deserialize, Deserializer, Serializer};
+            use $crate::ShortGuid;
+
+            /// Serializes the optional ID.
+            pub fn serialize<S>(id: &Option<ShortGuid>, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                match id {
+                    Some(id) => serializer.serialize_some(&Ser(id)),
+                    None => serializer.serialize_none(),
+                }
+            }
+
+            /// Deserializes the optional ID.
+            pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<ShortGuid>, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                Ok(Option::<De>::deserialize(deserializer)?.map(|De(id)| id))
+            }
+        }
+
+        /// The parent module's representation for `Vec<ShortGuid>`.
+        pub mod vec {
+            use super::{De, Ser};
+            use serde::{Deserialize, Deserializer, Serializer};
+            use $crate::ShortGuid;
+
+            /// Serializes the IDs as a sequence.
+            pub fn serialize<S>(ids: &[ShortGuid], serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                serializer.collect_seq(ids.iter().map(Ser))
+            }
+
+            /// Deserializes a sequence of IDs.
+            pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<ShortGuid>, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                let ids = Vec::<De>::deserialize(deserializer)?;
+                Ok(ids.into_iter().map(|De(id)| id).collect())
+            }
+        }
+    };
+}
+
+/// A visitor for IDs represented as strings, using the provided parser.
+struct StrVisitor {
+    expecting: &'static str,
+    parse: fn(&str) -> Result<ShortGuid, ParseError>,
+}
+
+impl<'vi> serde::de::Visitor<'vi> for StrVisitor {
+    type Value = ShortGuid;
+
+    fn expecting(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(formatter, "{}", self.expecting)
+    }
+
+    fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<ShortGuid, E> {
+        (self.parse)(value).map_err(de_error)
+    }
+}
+
+/// Lenient deserialization accepting the many shapes IDs take in the wild.
+///
+/// For human-readable formats, the following shapes are accepted:
+///
+/// * a [`ShortGuid`] or any [`Uuid`] string, such as `"yaZG05xhTLe_ze4lIsj2Mw"` or
+///   `"c9a646d3-9c61-4cb7-bfcd-ee2522c8f633"`,
+/// * 16 bytes, or a sequence of 16 byte values,
+/// * an unsigned integer holding the 128-bit value, such as
+///   `268038184965496707592766468059994584627`,
+/// * a map with the upper and lower 64 bits, such as
+///   `{"hi": 14530379122433150135, "lo": 13820964674734716467}`.
+///
+/// Note that many formats, such as `serde_json`, cannot represent integers beyond
+/// 64 bits when deserializing self-describing data and present them as floating-point
+/// numbers, which are rejected. Send such values as a string or use the map shape instead.
+///
+/// Binary formats are not self-describing and use the regular [`ShortGuid`] representation.
+/// Serialization always uses the regular representation.
+///
+/// ## Example
+///
+/// ```
+/// # use serde::Deserialize;
+/// # use shortguid::ShortGuid;
+/// #[derive(Deserialize)]
+/// struct Order {
+///     #[serde(with = "shortguid::serde::lenient")]
+///     id: ShortGuid,
+/// }
+///
+/// let expected = ShortGuid::try_parse("c9a646d3-9c61-4cb7-bfcd-ee2522c8f633").unwrap();
+/// for json in [
+///     r#"{"id": "yaZG05xhTLe_ze4lIsj2Mw"}"#,
+///     r#"{"id": "c9a646d3-9c61-4cb7-bfcd-ee2522c8f633"}"#,
+///     r#"{"id": {"hi": 14530379122433150135, "lo": 13820964674734716467}}"#,
+/// ] {
+///     let order: Order = serde_json::from_str(json).unwrap();
+///     assert_eq!(order.id, expected);
+/// }
+/// ```
+pub mod lenient {
+    use crate::ShortGuid;
+    use serde::de::{Error, MapAccess, SeqAccess, Unexpected, Visitor};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::fmt::Formatter;
+
+    /// Serializes the ID using the regular [`ShortGuid`] representation.
+    pub fn serialize<S>(id: &ShortGuid, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        id.serialize(serializer)
+    }
+
+    /// Deserializes the ID from any of the accepted shapes.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<ShortGuid, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(LenientVisitor)
+        } else {
+            ShortGuid::deserialize(deserializer)
+        }
+    }
+
+    struct LenientVisitor;
+
+    impl<'vi> Visitor<'vi> for LenientVisitor {
+        type Value = ShortGuid;
+
+        fn expecting(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+            write!(
+                formatter,
+                "one of: a ShortGuid or UUID string, 16 bytes, a sequence of 16 bytes, \
+                 an unsigned 128-bit integer, or a map with \"hi\" and \"lo\" 64-bit halves"
+            )
+        }
+
+        fn visit_str<E: Error>(self, value: &str) -> Result<ShortGuid, E> {
+            ShortGuid::try_parse(value).map_err(super::de_error)
+        }
+
+        fn visit_bytes<E: Error>(self, value: &[u8]) -> Result<ShortGuid, E> {
+            ShortGuid::from_slice(value).map_err(super::de_error)
+        }
+
+        fn visit_u64<E: Error>(self, value: u64) -> Result<ShortGuid, E> {
+            self.visit_u128(value as u128)
+        }
+
+        fn visit_i64<E: Error>(self, value: i64) -> Result<ShortGuid, E> {
+            match u64::try_from(value) {
+                Ok(value) => self.visit_u64(value),
+                Err(_) => Err(Error::invalid_value(Unexpected::Signed(value), &self)),
+            }
+        }
+
+        fn visit_u128<E: Error>(self, value: u128) -> Result<ShortGuid, E> {
+            Ok(ShortGuid::from_bytes(value.to_be_bytes()))
+        }
+
+        fn visit_f64<E: Error>(self, value: f64) -> Result<ShortGuid, E> {
+            Err(Error::invalid_type(
+                Unexpected::Float(value),
+                &"an integer; integers beyond 64 bits must be sent as a string \
+                  or a map with \"hi\" and \"lo\" 64-bit halves",
+            ))
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<ShortGuid, A::Error>
+        where
+            A: SeqAccess<'vi>,
+        {
+            let mut bytes = [0u8; 16];
+            for (i, byte) in bytes.iter_mut().enumerate() {
+                *byte = seq
+                    .next_element()?
+                    .ok_or_else(|| Error::invalid_length(i, &self))?;
+            }
+            if seq.next_element::<u8>()?.is_some() {
+                return Err(Error::invalid_length(17, &self));
+            }
+            Ok(ShortGuid::from_bytes(bytes))
+        }
+
+        fn visit_map<A>(self, mut map: A) -> Result<ShortGuid, A::Error>
+        where
+            A: MapAccess<'vi>,
+        {
+            let mut hi: Option<u64> = None;
+            let mut lo: Option<u64> = None;
+            while let Some(key) = map.next_key::<String>()? {
+                let half = match key.as_str() {
+                    "hi" => &mut hi,
+                    "lo" => &mut lo,
+                    _ => return Err(Error::unknown_field(&key, &["hi", "lo"])),
+                };
+                if half.is_some() {
+                    return Err(Error::custom(format_args!("duplicate field `{key}`")));
+                }
+                *half = Some(map.next_value()?);
+            }
+
+            let hi = hi.ok_or_else(|| Error::missing_field("hi"))?;
+            let lo = lo.ok_or_else(|| Error::missing_field("lo"))?;
+            self.visit_u128((hi as u128) << 64 | lo as u128)
+        }
+    }
+
+    with_collections!();
+}
+
+/// Always represents the ID as a hyphenated UUID string, such as
+/// `"c9a646d3-9c61-4cb7-bfcd-ee2522c8f633"`.
+///
+/// Deserialization accepts any UUID string representation.
+///
+/// ## Example
+///
+/// ```
+/// # use serde::{Deserialize, Serialize};
+/// # use shortguid::ShortGuid;
+/// #[derive(Serialize, Deserialize)]
+/// struct Order {
+///     #[serde(with = "shortguid::serde::hyphenated")]
+///     id: ShortGuid,
+///     #[serde(with = "shortguid::serde::hyphenated::option")]
+///     parent: Option<ShortGuid>,
+/// }
+///
+/// let id = ShortGuid::try_parse("yaZG05xhTLe_ze4lIsj2Mw").unwrap();
+/// let json = serde_json::to_string(&Order { id, parent: None }).unwrap();
+/// assert_eq!(json, r#"{"id":"c9a646d3-9c61-4cb7-bfcd-ee2522c8f633","parent":null}"#);
+/// ```
+pub mod hyphenated {
+    use super::StrVisitor;
+    use crate::{Format, ShortGuid};
+    use serde::{Deserializer, Serializer};
+
+    /// Serializes the ID as a hyphenated UUID string.
+    pub fn serialize<S>(id: &ShortGuid, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(&id.as_uuid().hyphenated())
+    }
+
+    /// Deserializes the ID from a UUID string.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<ShortGuid, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(StrVisitor {
+            expecting: "a UUID string",
+            parse: |value| Format::Uuid.parse(value),
+        })
+    }
+
+    with_collections!();
+}
+
+/// Always represents the ID as a 22 character [`ShortGuid`] string, such as
+/// `"yaZG05xhTLe_ze4lIsj2Mw"`, even for binary formats.
+pub mod short {
+    use super::StrVisitor;
+    use crate::{Format, ShortGuid};
+    use serde::{Deserializer, Serializer};
+
+    /// Serializes the ID as a [`ShortGuid`] string.
+    pub fn serialize<S>(id: &ShortGuid, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(id)
+    }
+
+    /// Deserializes the ID from a 22 character [`ShortGuid`] string; the empty string is rejected.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<ShortGuid, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(StrVisitor {
+            expecting: "a ShortGuid string",
+            parse: |value| Format::Short.parse(value),
+        })
+    }
+
+    with_collections!();
+}
+
+/// Always represents the ID as 16 bytes, even for human-readable formats.
+///
+/// Deserialization accepts bytes as well as a sequence of 16 byte values, which is
+/// how formats such as JSON represent bytes.
+///
+/// The `map` submodule requires a format that supports bytes as map keys, such as
+/// bincode, postcard or CBOR; JSON does not.
+pub mod bytes_array {
+    use crate::ShortGuid;
+    use serde::de::{Error, SeqAccess, Visitor};
+    use serde::{Deserializer, Serializer};
+    use std::fmt::Formatter;
+
+    /// Serializes the ID as bytes.
+    pub fn serialize<S>(id: &ShortGuid, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(id.as_bytes())
+    }
+
+    /// Deserializes the ID from bytes or a sequence of byte values.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<ShortGuid, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_bytes(BytesVisitor)
+    }
+
+    struct BytesVisitor;
+
+    impl<'vi> Visitor<'vi> for BytesVisitor {
+        type Value = ShortGuid;
+
+        fn expecting(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+            write!(formatter, "16 bytes")
+        }
+
+        fn visit_bytes<E: Error>(self, value: &[u8]) -> Result<ShortGuid, E> {
+            ShortGuid::from_slice(value).map_err(super::de_error)
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<ShortGuid, A::Error>
+        where
+            A: SeqAccess<'vi>,
+        {
+            let mut bytes = [0u8; 16];
+            for (i, byte) in bytes.iter_mut().enumerate() {
+                *byte = seq
+                    .next_element()?
+                    .ok_or_else(|| Error::invalid_length(i, &self))?;
+            }
+            if seq.next_element::<u8>()?.is_some() {
+                return Err(Error::invalid_length(17, &self));
+            }
+            Ok(ShortGuid::from_bytes(bytes))
+        }
+    }
+
+    with_collections!();
+}
+
+/// Always represents the ID as a tuple of 16 byte values.
+///
+/// Unlike bytes, a tuple has a fixed size and is written without a length prefix by
+/// binary formats such as bincode and postcard, which saves up to eight bytes per ID.
+///
+/// Deserialization accepts the tuple as well as bytes in self-describing formats.
+/// Formats that are not self-describing, such as bincode and postcard, cannot tell the
+/// two layouts apart: [`fixed_array::deserialize`] reads the tuple, while
+/// [`fixed_array::deserialize_bytes`] reads the length-prefixed bytes written by the
+/// regular representation, e.g. to migrate existing data to the tuple layout.
+///
+/// The `map` submodule requires a format that supports tuples as map keys, such as
+/// bincode, postcard or CBOR; JSON does not.
+///
+/// ## Example
+///
+/// ```
+/// # use serde::{Deserialize, Serialize};
+/// # use shortguid::ShortGuid;
+/// #[derive(Serialize, Deserialize)]
+/// struct Record {
+///     #[serde(with = "shortguid::serde::fixed_array")]
+///     id: ShortGuid,
+/// }
+///
+/// let record = Record { id: ShortGuid::new_random() };
+/// let bytes = postcard::to_allocvec(&record).unwrap();
+/// assert_eq!(bytes.len(), 16);
+///
+/// #[derive(Deserialize)]
+/// struct Legacy {
+///     #[serde(deserialize_with = "shortguid::serde::fixed_array::deserialize_bytes")]
+///     id: ShortGuid,
+/// }
+///
+/// let bytes = postcard::to_allocvec(&record.id).unwrap();
+/// let legacy: Legacy = postcard::from_bytes(&bytes).unwrap();
+/// assert_eq!(legacy.id, record.id);
+/// ```
+pub mod fixed_array {
+    use crate::ShortGuid;
+    use serde::de::{Error, IgnoredAny, SeqAccess, Visitor};
+    use serde::ser::SerializeTuple;
+    use serde::{Deserializer, Serializer};
+    use std::fmt::Formatter;
+
+    /// Serializes the ID as a tuple of 16 byte values.
+    pub fn serialize<S>(id: &ShortGuid, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut tuple = serializer.serialize_tuple(16)?;
+        for byte in id.as_bytes() {
+            tuple.serialize_element(byte)?;
+        }
+        tuple.end()
+    }
+
+    /// Deserializes the ID from a tuple of 16 byte values, or from bytes.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<ShortGuid, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(16, TupleVisitor)
+    }
+
+    /// Deserializes the ID from bytes, or from a tuple of 16 byte values.
+    ///
+    /// Unlike [`deserialize`], this reads the length-prefixed layout of the regular
+    /// representation from formats that are not self-describing.
+    pub fn deserialize_bytes<'de, D>(deserializer: D) -> Result<ShortGuid, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_bytes(TupleVisitor)
+    }
+
+    struct TupleVisitor;
+
+    impl<'vi> Visitor<'vi> for TupleVisitor {
+        type Value = ShortGuid;
+
+        fn expecting(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+            write!(formatter, "a tuple of 16 bytes")
+        }
+
+        fn visit_bytes<E: Error>(self, value: &[u8]) -> Result<ShortGuid, E> {
+            ShortGuid::from_slice(value).map_err(super::de_error)
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<ShortGuid, A::Error>
+        where
+            A: SeqAccess<'vi>,
+        {
+            let mut bytes = [0u8; 16];
+            for (i, byte) in bytes.iter_mut().enumerate() {
+                *byte = seq
+                    .next_element()?
+                    .ok_or_else(|| Error::invalid_length(i, &self))?;
+            }
+            if seq.next_element::<IgnoredAny>()?.is_some() {
+                return Err(Error::invalid_length(17, &self));
+            }
+            Ok(ShortGuid::from_bytes(bytes))
+        }
+    }
+
+    with_collections!();
+}
+
+/// Always represents the ID as an unsigned 128-bit integer.
+///
+/// Note that not all formats support 128-bit integers.
+pub mod u128 {
+    use crate::ShortGuid;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    /// Serializes the ID as an unsigned 128-bit integer.
+    pub fn serialize<S>(id: &ShortGuid, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u128(id.as_uuid().as_u128())
+    }
+
+    /// Deserializes the ID from an unsigned 128-bit integer.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<ShortGuid, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = u128::deserialize(deserializer)?;
+        Ok(ShortGuid::from_bytes(value.to_be_bytes()))
+    }
+
+    with_collections!();
+}
+
+/// Always represents the ID as a padded standard Base64 string using `+` and `/`,
+/// such as `"yaZG05xhTLe/ze4lIsj2Mw=="`, as used by e.g. .NET and Protobuf JSON.
+pub mod standard_base64 {
+    use super::StrVisitor;
+    use crate::ShortGuid;
+    use base64::Engine;
+    use serde::{Deserializer, Serializer};
+
+    /// Serializes the ID as a standard Base64 string.
+    pub fn serialize<S>(id: &ShortGuid, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let engine = &base64::engine::general_purpose::STANDARD;
+        serializer.serialize_str(&engine.encode(id.as_bytes()))
+    }
+
+    /// Deserializes the ID from a standard Base64 string.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<ShortGuid, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(StrVisitor {
+            expecting: "a standard Base64 string",
+            parse: |value| {
+                let engine = &base64::engine::general_purpose::STANDARD;
+                ShortGuid::from_slice(engine.decode(value)?)
+            },
+        })
+    }
+
+    with_collections!();
+}
+
+/// Represents the ID as a CBOR byte string wrapped in the IANA semantic tag 37,
+/// which marks the 16 bytes as a UUID for other CBOR consumers.
+///
+/// Deserialization accepts the tagged bytes as well as untagged bytes and text strings
+/// holding a [`ShortGuid`] or a hyphenated [`Uuid`]. Other tags are rejected.
+///
+/// This representation relies on the tag support of `ciborium` and should only be used with it.
+///
+/// ## Example
+///
+/// ```
+/// # use serde::{Deserialize, Serialize};
+/// # use shortguid::ShortGuid;
+/// #[derive(Serialize, Deserialize)]
+/// struct Order {
+///     #[serde(with = "shortguid::serde::cbor")]
+///     id: ShortGuid,
+/// }
+///
+/// let order = Order { id: ShortGuid::try_parse("yaZG05xhTLe_ze4lIsj2Mw").unwrap() };
+/// let mut bytes = Vec::new();
+/// ciborium::into_writer(&order, &mut bytes).unwrap();
+///
+/// // The 16 bytes (0x50) are preceded by tag 37 (0xd8 0x25).
+/// assert_eq!(&bytes[4..7], &[0xd8, 0x25, 0x50]);
+/// assert_eq!(&bytes[7..], order.id.as_bytes());
+/// ```
+#[cfg(feature = "cbor")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cbor")))]
+pub mod cbor {
+    use crate::ShortGuid;
+    use ciborium::tag::Accepted;
+    use serde::de::{Error, Visitor};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::fmt::Formatter;
+
+    /// The IANA CBOR tag for a binary UUID.
+    pub const UUID_TAG: u64 = 37;
+
+    /// Serializes the ID as a byte string with tag 37.
+    pub fn serialize<S>(id: &ShortGuid, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        Accepted::<_, UUID_TAG>(Payload(*id)).serialize(serializer)
+    }
+
+    /// Deserializes the ID from a byte string with or without tag 37, or from a text string.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<ShortGuid, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let Accepted(Payload(id)) = Accepted::<Payload, UUID_TAG>::deserialize(deserializer)?;
+        Ok(id)
+    }
+
+    /// The tagged data item.
+    struct Payload(ShortGuid);
+
+    impl Serialize for Payload {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            serializer.serialize_bytes(self.0.as_bytes())
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Payload {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_any(PayloadVisitor).map(Payload)
+        }
+    }
+
+    struct PayloadVisitor;
+
+    impl<'vi> Visitor<'vi> for PayloadVisitor {
+        type Value = ShortGuid;
+
+        fn expecting(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+            write!(formatter, "16 bytes or a ShortGuid string")
+        }
+
+        fn visit_str<E: Error>(self, value: &str) -> Result<ShortGuid, E> {
+            ShortGuid::try_parse(value).map_err(super::de_error)
+        }
+
+        fn visit_bytes<E: Error>(self, value: &[u8]) -> Result<ShortGuid, E> {
+            ShortGuid::from_slice(value).map_err(super::de_error)
+        }
+    }
+
+    with_collections!();
+}
+
+/// Represents the ID as a BSON binary with the UUID subtype 4, which interoperates
+/// with the UUID handling of other MongoDB drivers.
+///
+/// The regular [`ShortGuid`] representation is stored as a string by `bson`, or as a
+/// generic binary (subtype 0) if the serializer is configured to not be human-readable.
+/// Deserialization using this module only accepts the subtype 4; use the `csharp_legacy`,
+/// `java_legacy` or `python_legacy` submodules to read the legacy subtype 3 written by
+/// the respective driver.
+///
+/// BSON documents only have string keys, so unlike the other representations, this module
+/// provides no `map` submodule.
+///
+/// ## Example
+///
+/// ```
+/// # use serde::{Deserialize, Serialize};
+/// # use shortguid::ShortGuid;
+/// use bson::spec::BinarySubtype;
+/// use bson::Bson;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Order {
+///     #[serde(with = "shortguid::serde::bson_binary")]
+///     id: ShortGuid,
+/// }
+///
+/// let order = Order { id: ShortGuid::try_parse("yaZG05xhTLe_ze4lIsj2Mw").unwrap() };
+/// let document = bson::to_document(&order).unwrap();
+/// match document.get("id") {
+///     Some(Bson::Binary(binary)) => assert_eq!(binary.subtype, BinarySubtype::Uuid),
+///     other => panic!("unexpected value {other:?}"),
+/// }
+/// ```
+#[cfg(feature = "bson")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bson")))]
+pub mod bson_binary {
+    use crate::ShortGuid;
+    use bson::spec::BinarySubtype;
+    use bson::{Binary, UuidRepresentation};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    /// Serializes the ID as a BSON binary with subtype 4.
+    pub fn serialize<S>(id: &ShortGuid, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        id.to_bson_binary().serialize(serializer)
+    }
+
+    /// Deserializes the ID from a BSON binary with subtype 4.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<ShortGuid, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_with(deserializer, UuidRepresentation::Standard)
+    }
+
+    /// Deserializes the ID from a BSON binary with subtype 4, or with subtype 3
+    /// in the given legacy representation.
+    fn deserialize_with<'de, D>(
+        deserializer: D,
+        legacy: UuidRepresentation,
+    ) -> Result<ShortGuid, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let binary = Binary::deserialize(deserializer)?;
+        let representation = match binary.subtype {
+            BinarySubtype::UuidOld => legacy,
+            _ => UuidRepresentation::Standard,
+        };
+        ShortGuid::from_bson_binary(&binary, representation).map_err(super::de_error)
+    }
+
+    /// Generates a submodule that writes the subtype 4 and additionally reads the
+    /// subtype 3 in the given legacy representation.
+    macro_rules! legacy_module {
+        ($(#[$meta:meta])* $name:ident, $representation:ident) => {
+            $(#[$meta])*
+            pub mod $name {
+                use bson::UuidRepresentation;
+                use serde::{Deserializer, Serializer};
+                use $crate::ShortGuid;
+
+                /// Serializes the ID as a BSON binary with subtype 4.
+                pub fn serialize<S>(id: &ShortGuid, serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    S: Serializer,
+                {
+                    super::serialize(id, serializer)
+                }
+
+                /// Deserializes the ID from a BSON binary with subtype 4, or with the
+                /// legacy subtype 3.
+                pub fn deserialize<'de, D>(deserializer: D) -> Result<ShortGuid, D::Error>
+                where
+                    D: Deserializer<'de>,
+                {
+                    super::deserialize_with(deserializer, UuidRepresentation::$representation)
+                }
+
+                with_collections!(without_map);
+            }
+        };
+    }
+
+    legacy_module!(
+        /// Reads the legacy subtype 3 written by the C# driver, which stores the first three
+        /// groups of the UUID in little-endian byte order. Always writes the subtype 4.
+        csharp_legacy,
+        CSharpLegacy
+    );
+
+    legacy_module!(
+        /// Reads the legacy subtype 3 written by the Java driver, which stores both 8 byte
+        /// halves of the UUID in reversed byte order. Always writes the subtype 4.
+        java_legacy,
+        JavaLegacy
+    );
+
+    legacy_module!(
+        /// Reads the legacy subtype 3 written by the Python driver, which stores the bytes
+        /// in their regular order. Always writes the subtype 4.
+        python_legacy,
+        PythonLegacy
+    );
+
+    with_collections!(without_map);
+}
@@ -0,0 +1,452 @@
+use crate::ShortGuid;
+use sqlx::error::BoxDynError;
+use sqlx::{Database, Decode, Encode, Type};
+
+/// A [`ShortGuid`] stored as its 22 character string in a `TEXT` column.
+///
+/// A [`ShortGuid`] itself uses the database's UUID or binary type. Use this wrapper
+/// for columns that should instead hold the readable form, e.g. `TEXT` columns in SQLite.
+/// Decoding also accepts the hyphenated form and the other formats of [`ShortGuid::try_parse`],
+/// but rejects empty text rather than reading it as the nil ID.
+///
+/// ```
+/// use shortguid::{ShortGuid, TextShortGuid};
+///
+/// let id = ShortGuid::try_parse("yaZG05xhTLe_ze4lIsj2Mw").unwrap();
+/// let text = TextShortGuid::from(id);
+/// assert_eq!(text.0, id);
+/// ```
+#[derive(Copy, Clone, Default, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct TextShortGuid(pub ShortGuid);
+
+impl From<ShortGuid> for TextShortGuid {
+    fn from(value: ShortGuid) -> Self {
+        Self(value)
+    }
+}
+
+impl From<TextShortGuid> for ShortGuid {
+    fn from(value: TextShortGuid) -> Self {
+        value.0
+    }
+}
+
+impl<DB: Database> Type<DB> for TextShortGuid
+where
+    str: Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <str as Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <str as Type<DB>>::compatible(ty)
+    }
+}
+
+impl<'q, DB: Database> Encode<'q, DB> for TextShortGuid
+where
+    String: Encode<'q, DB>,
+{
+    fn encode_by_ref(
+        &self,
+        buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+    ) -> Result<sqlx::encode::IsNull, BoxDynError> {
+        self.0.to_string().encode(buf)
+    }
+
+    fn size_hint(&self) -> usize {
+        22
+    }
+}
+
+impl<'r, DB: Database> Decode<'r, DB> for TextShortGuid
+where
+    &'r str: Decode<'r, DB>,
+{
+    fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+        let value = <&str as Decode<DB>>::decode(value)?;
+        Ok(Self(ShortGuid::try_parse_non_empty(value)?))
+    }
+}
+
+/// Reads a [`ShortGuid`] from a binary value with 16 bytes, or from a text value
+/// in any of the formats accepted by [`ShortGuid::try_parse`] except the empty string.
+///
+/// The representation is chosen by the type of the value, not by its length.
+#[cfg(any(feature = "sqlx-mysql", feature = "sqlx-sqlite"))]
+fn decode_binary_or_text<'r, DB: Database>(
+    value: <DB as Database>::ValueRef<'r>,
+) -> Result<ShortGuid, BoxDynError>
+where
+    str: Type<DB>,
+    &'r str: Decode<'r, DB>,
+    &'r [u8]: Decode<'r, DB>,
+{
+    use sqlx::ValueRef;
+
+    if <str as Type<DB>>::compatible(&value.type_info()) {
+        let value = <&str as Decode<DB>>::decode(value)?;
+        Ok(ShortGuid::try_parse_non_empty(value)?)
+    } else {
+        let bytes = <&[u8] as Decode<DB>>::decode(value)?;
+        Ok(ShortGuid::from_slice(bytes)?)
+    }
+}
+
+#[cfg(feature = "sqlx-postgres")]
+mod postgres {
+    use super::TextShortGuid;
+    use crate::ShortGuid;
+    use sqlx::encode::IsNull;
+    use sqlx::error::BoxDynError;
+    use sqlx::postgres::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueRef, Postgres};
+    use sqlx::{Decode, Encode, Type};
+    use uuid::Uuid;
+
+    /// Uses the native `UUID` type.
+    impl Type<Postgres> for ShortGuid {
+        fn type_info() -> PgTypeInfo {
+            <Uuid as Type<Postgres>>::type_info()
+        }
+    }
+
+    impl PgHasArrayType for ShortGuid {
+        fn array_type_info() -> PgTypeInfo {
+            <Uuid as PgHasArrayType>::array_type_info()
+        }
+    }
+
+    impl Encode<'_, Postgres> for ShortGuid {
+        fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+            <Uuid as Encode<Postgres>>::encode_by_ref(self.as_uuid(), buf)
+        }
+
+        fn size_hint(&self) -> usize {
+            16
+        }
+    }
+
+    impl Decode<'_, Postgres> for ShortGuid {
+        fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+            let uuid = <Uuid as Decode<Postgres>>::decode(value)?;
+            Ok(ShortGuid::from(uuid))
+        }
+    }
+
+    impl PgHasArrayType for TextShortGuid {
+        fn array_type_info() -> PgTypeInfo {
+            <String as PgHasArrayType>::array_type_info()
+        }
+    }
+}
+
+#[cfg(feature = "sqlx-mysql")]
+mod mysql {
+    use super::decode_binary_or_text;
+    use crate::ShortGuid;
+    use sqlx::encode::IsNull;
+    use sqlx::error::BoxDynError;
+    use sqlx::mysql::{MySql, MySqlTypeInfo, MySqlValueRef};
+    use sqlx::{Decode, Encode, Type};
+    use uuid::Uuid;
+
+    /// Uses `BINARY(16)`; decoding also accepts text columns.
+    impl Type<MySql> for ShortGuid {
+        fn type_info() -> MySqlTypeInfo {
+            <Uuid as Type<MySql>>::type_info()
+        }
+
+        fn compatible(ty: &MySqlTypeInfo) -> bool {
+            <[u8] as Type<MySql>>::compatible(ty) || <str as Type<MySql>>::compatible(ty)
+        }
+    }
+
+    impl Encode<'_, MySql> for ShortGuid {
+        fn encode_by_ref(&self, buf: &mut Vec<u8>) -> Result<IsNull, BoxDynError> {
+            <Uuid as Encode<MySql>>::encode_by_ref(self.as_uuid(), buf)
+        }
+    }
+
+    impl Decode<'_, MySql> for ShortGuid {
+        fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
+            decode_binary_or_text::<MySql>(value)
+        }
+    }
+}
+
+#[cfg(feature = "sqlx-sqlite")]
+mod sqlite {
+    use super::decode_binary_or_text;
+    use crate::ShortGuid;
+    use sqlx::encode::IsNull;
+    use sqlx::error::BoxDynError;
+    use sqlx::sqlite::{Sqlite, SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef};
+    use sqlx::{Decode, Encode, Type};
+    use uuid::Uuid;
+
+    /// Uses `BLOB`; decoding also accepts `TEXT` columns.
+    impl Type<Sqlite> for ShortGuid {
+        fn type_info() -> SqliteTypeInfo {
+            <Uuid as Type<Sqlite>>::type_info()
+        }
+
+        fn compatible(ty: &SqliteTypeInfo) -> bool {
+            <Uuid as Type<Sqlite>>::compatible(ty)
+        }
+    }
+
+    impl<'q> Encode<'q, Sqlite> for ShortGuid {
+        fn encode_by_ref(
+            &self,
+            args: &mut Vec<SqliteArgumentValue<'q>>,
+        ) -> Result<IsNull, BoxDynError> {
+            <Uuid as Encode<Sqlite>>::encode_by_ref(self.as_uuid(), args)
+        }
+    }
+
+    impl Decode<'_, Sqlite> for ShortGuid {
+        fn decode(value: SqliteValueRef<'_>) -> Result<Self, BoxDynError> {
+            decode_binary_or_text::<Sqlite>(value)
+        }
+    }
+}
+
+#[cfg(all(test, feature = "sqlx-sqlite"))]
+mod sqlite_tests {
+    use super::*;
+    use sqlx::{Connection, Row, SqliteConnection};
+
+    const ID: &str = "yaZG05xhTLe_ze4lIsj2Mw";
+
+    async fn connect() -> SqliteConnection {
+        let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+        sqlx::query("CREATE TABLE ids (blob_id BLOB, text_id TEXT)")
+            .execute(&mut conn)
+            .await
+            .unwrap();
+        conn
+    }
+
+    #[tokio::test]
+    async fn test_blob_and_text() {
+        let id = ShortGuid::try_parse(ID).unwrap();
+        let mut conn = connect().await;
+        sqlx::query("INSERT INTO ids (blob_id, text_id) VALUES (?, ?)")
+            .bind(id)
+            .bind(TextShortGuid(id))
+            .execute(&mut conn)
+            .await
+            .unwrap();
+
+        let row = sqlx::query("SELECT blob_id, text_id, typeof(blob_id), typeof(text_id) FROM ids")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(row.get::<ShortGuid, _>(0), id);
+        assert_eq!(row.get::<TextShortGuid, _>(1), TextShortGuid(id));
+        assert_eq!(row.get::<ShortGuid, _>(1), id);
+        assert_eq!(row.get::<&str, _>(2), "blob");
+        assert_eq!(row.get::<&str, _>(3), "text");
+        assert_eq!(row.get::<String, _>(1), ID);
+    }
+
+    #[tokio::test]
+    async fn test_query_by_id() {
+        let id = ShortGuid::new_random();
+        let mut conn = connect().await;
+        for other in [ShortGuid::new_random(), id, ShortGuid::new_random()] {
+            sqlx::query("INSERT INTO ids (blob_id) VALUES (?)")
+                .bind(other)
+                .execute(&mut conn)
+                .await
+                .unwrap();
+        }
+
+        let found: Vec<(ShortGuid,)> = sqlx::query_as("SELECT blob_id FROM ids WHERE blob_id = ?")
+            .bind(id)
+            .fetch_all(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(found, [(id,)]);
+
+        let missing: Option<ShortGuid> = sqlx::query_scalar("SELECT text_id FROM ids")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(missing, None);
+    }
+
+    #[tokio::test]
+    async fn test_decode_hyphenated_text() {
+        let mut conn = connect().await;
+        let id: ShortGuid = sqlx::query_scalar("SELECT 'c9a646d3-9c61-4cb7-bfcd-ee2522c8f633'")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(id, ID);
+    }
+
+    #[tokio::test]
+    async fn test_decode_by_type() {
+        let mut conn = connect().await;
+
+        // 16 characters of text are not mistaken for bytes.
+        let result: Result<ShortGuid, _> = sqlx::query_scalar("SELECT 'F9168C5E-CEB2-4F'")
+            .fetch_one(&mut conn)
+            .await;
+        assert!(matches!(result, Err(sqlx::Error::ColumnDecode { .. })));
+
+        // 22 bytes are not mistaken for text.
+        let result: Result<ShortGuid, _> = sqlx::query_scalar("SELECT CAST(? AS BLOB)")
+            .bind(ID)
+            .fetch_one(&mut conn)
+            .await;
+        assert!(matches!(result, Err(sqlx::Error::ColumnDecode { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_decode_invalid() {
+        let mut conn = connect().await;
+        let result: Result<ShortGuid, _> = sqlx::query_scalar("SELECT x'c9a646d3'")
+            .fetch_one(&mut conn)
+            .await;
+        assert!(matches!(result, Err(sqlx::Error::ColumnDecode { .. })));
+
+        let result: Result<TextShortGuid, _> = sqlx::query_scalar("SELECT 'yaZG05xhTLe'")
+            .fetch_one(&mut conn)
+            .await;
+        assert!(matches!(result, Err(sqlx::Error::ColumnDecode { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_decode_empty_text() {
+        let mut conn = connect().await;
+        let result: Result<TextShortGuid, _> =
+            sqlx::query_scalar("SELECT ''").fetch_one(&mut conn).await;
+        assert!(matches!(result, Err(sqlx::Error::ColumnDecode { .. })));
+
+        let result: Result<ShortGuid, _> =
+            sqlx::query_scalar("SELECT ''").fetch_one(&mut conn).await;
+        assert!(matches!(result, Err(sqlx::Error::ColumnDecode { .. })));
+    }
+}
+
+#[cfg(all(test, feature = "sqlx-postgres"))]
+mod postgres_tests {
+    use super::*;
+    use sqlx::postgres::{PgHasArrayType, Postgres};
+    use sqlx::{Connection, PgConnection, TypeInfo};
+
+    #[test]
+    fn test_type_info() {
+        assert_eq!(<ShortGuid as Type<Postgres>>::type_info().name(), "UUID");
+        assert_eq!(ShortGuid::array_type_info().name(), "UUID[]");
+        assert_eq!(
+            <TextShortGuid as Type<Postgres>>::type_info().name(),
+            "TEXT"
+        );
+    }
+
+    /// Connects to the Postgres server in `DATABASE_URL`.
+    async fn connect() -> PgConnection {
+        let url = std::env::var("DATABASE_URL")
+            .expect("DATABASE_URL must point to a Postgres server to run these tests");
+        assert!(
+            url.starts_with("postgres"),
+            "DATABASE_URL is not a Postgres URL: {url}"
+        );
+        PgConnection::connect(&url).await.unwrap()
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a Postgres server in DATABASE_URL"]
+    async fn test_roundtrip() {
+        let mut conn = connect().await;
+        let id = ShortGuid::new_random();
+        let (uuid, text, ids): (uuid::Uuid, String, Vec<ShortGuid>) =
+            sqlx::query_as("SELECT $1::uuid, $1::text, ARRAY[$1, $2]")
+                .bind(id)
+                .bind(ShortGuid::default())
+                .fetch_one(&mut conn)
+                .await
+                .unwrap();
+        assert_eq!(id, uuid);
+        assert_eq!(text, id.as_uuid().to_string());
+        assert_eq!(ids, [id, ShortGuid::default()]);
+
+        let text: TextShortGuid = sqlx::query_scalar("SELECT $1")
+            .bind(TextShortGuid(id))
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(text.0, id);
+    }
+}
+
+#[cfg(all(test, feature = "sqlx-mysql"))]
+mod mysql_tests {
+    use super::*;
+    use sqlx::mysql::MySql;
+    use sqlx::{Connection, MySqlConnection, TypeInfo};
+
+    #[test]
+    fn test_type_info() {
+        // Binary parameters are sent as BLOB, which is also stored in BINARY(16) columns.
+        assert_eq!(<ShortGuid as Type<MySql>>::type_info().name(), "BLOB");
+        assert_eq!(
+            <TextShortGuid as Type<MySql>>::type_info().name(),
+            "VARCHAR"
+        );
+    }
+
+    #[test]
+    fn test_compatible() {
+        let binary = <[u8] as Type<MySql>>::type_info();
+        let text = <str as Type<MySql>>::type_info();
+        assert!(<ShortGuid as Type<MySql>>::compatible(&binary));
+        assert!(<ShortGuid as Type<MySql>>::compatible(&text));
+        assert!(<TextShortGuid as Type<MySql>>::compatible(&text));
+        assert!(!<TextShortGuid as Type<MySql>>::compatible(&binary));
+        assert!(!<ShortGuid as Type<MySql>>::compatible(&<i64 as Type<
+            MySql,
+        >>::type_info(
+        )));
+    }
+
+    /// Connects to the MySQL server in `DATABASE_URL`.
+    async fn connect() -> MySqlConnection {
+        let url = std::env::var("DATABASE_URL")
+            .expect("DATABASE_URL must point to a MySQL server to run these tests");
+        assert!(
+            url.starts_with("mysql"),
+            "DATABASE_URL is not a MySQL URL: {url}"
+        );
+        MySqlConnection::connect(&url).await.unwrap()
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a MySQL server in DATABASE_URL"]
+    async fn test_roundtrip() {
+        let mut conn = connect().await;
+        let id = ShortGuid::new_random();
+        let (binary, text, hyphenated): (ShortGuid, ShortGuid, ShortGuid) =
+            sqlx::query_as("SELECT ?, ?, ?")
+                .bind(id)
+                .bind(TextShortGuid(id))
+                .bind(id.as_uuid().to_string())
+                .fetch_one(&mut conn)
+                .await
+                .unwrap();
+        assert_eq!(binary, id);
+        assert_eq!(text, id);
+        assert_eq!(hyphenated, id);
+
+        let text: TextShortGuid = sqlx::query_scalar("SELECT ?")
+            .bind(TextShortGuid(id))
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(text.0, id);
+    }
+}
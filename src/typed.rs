@@ -0,0 +1,362 @@
+use crate::{ParseError, ShortGuid};
+use std::cmp::Ordering;
+use std::fmt::{Debug, Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Provides the compile-time prefix of a [`TypedShortGuid`].
+///
+/// ## Example
+///
+/// ```
+/// # use shortguid::{IdPrefix, TypedShortGuid};
+/// enum User {}
+///
+/// impl IdPrefix for User {
+///     const PREFIX: &'static str = "user";
+/// }
+///
+/// let id = TypedShortGuid::<User>::try_parse("user_yaZG05xhTLe_ze4lIsj2Mw").unwrap();
+/// assert_eq!(id.to_string(), "user_yaZG05xhTLe_ze4lIsj2Mw");
+/// ```
+pub trait IdPrefix {
+    /// The prefix, rendered in front of the ID and separated by an underscore.
+    const PREFIX: &'static str;
+}
+
+/// A [`ShortGuid`] tagged with a type-specific prefix, e.g. `user_yaZG05xhTLe_ze4lIsj2Mw`.
+///
+/// Two typed IDs with different [`IdPrefix`] types are distinct types and cannot be
+/// compared or swapped by accident. Parsing rejects values carrying a different prefix.
+///
+/// The type has the same memory layout as [`ShortGuid`].
+///
+/// ## Example
+///
+/// ```
+/// # use shortguid::{IdPrefix, ParseError, ShortGuid, TypedShortGuid};
+/// enum Order {}
+///
+/// impl IdPrefix for Order {
+///     const PREFIX: &'static str = "order";
+/// }
+///
+/// type OrderId = TypedShortGuid<Order>;
+///
+/// let id = OrderId::try_parse("order_yaZG05xhTLe_ze4lIsj2Mw").unwrap();
+/// assert_eq!(id.as_short_guid(), &ShortGuid::try_parse("yaZG05xhTLe_ze4lIsj2Mw").unwrap());
+///
+/// assert_eq!(
+///     OrderId::try_parse("user_yaZG05xhTLe_ze4lIsj2Mw").unwrap_err(),
+///     ParseError::InvalidPrefix { expected: "order" }
+/// );
+/// ```
+#[repr(transparent)]
+pub struct TypedShortGuid<T: IdPrefix> {
+    id: ShortGuid,
+    _prefix: PhantomData<fn() -> T>,
+}
+
+impl<T: IdPrefix> TypedShortGuid<T> {
+    /// Generates a new [`TypedShortGuid`] based on a random UUID v4.
+    #[cfg_attr(docsrs, doc(cfg(feature = "random")))]
+    #[cfg(feature = "random")]
+    #[inline(always)]
+    pub fn new_random() -> Self {
+        Self::new(ShortGuid::new_random())
+    }
+
+    /// Tags the provided [`ShortGuid`] with the prefix of `T`.
+    #[inline(always)]
+    pub const fn new(id: ShortGuid) -> Self {
+        Self {
+            id,
+            _prefix: PhantomData,
+        }
+    }
+
+    /// Creates a new [`TypedShortGuid`] based on the provided [`Uuid`].
+    #[inline(always)]
+    pub const fn new_from_uuid(uuid: Uuid) -> Self {
+        Self::new(ShortGuid::new_from_uuid(uuid))
+    }
+
+    /// Tries to parse a prefixed value such as `user_yaZG05xhTLe_ze4lIsj2Mw`.
+    ///
+    /// The part following the prefix may be any representation accepted by
+    /// [`ShortGuid::try_parse`].
+    pub fn try_parse<S: AsRef<str>>(value: S) -> Result<Self, ParseError> {
+        parse_prefixed(value.as_ref(), T::PREFIX).map(Self::new)
+    }
+
+    /// Returns the prefix of this ID type.
+    #[inline(always)]
+    pub const fn prefix() -> &'static str {
+        T::PREFIX
+    }
+
+    /// Returns the underlying [`ShortGuid`] instance.
+    #[inline]
+    pub const fn as_short_guid(&self) -> &ShortGuid {
+        &self.id
+    }
+
+    /// Removes the type tag and returns the underlying [`ShortGuid`].
+    #[inline]
+    pub const fn into_short_guid(self) -> ShortGuid {
+        self.id
+    }
+
+    /// Returns the underlying [`Uuid`] instance.
+    #[inline]
+    pub const fn as_uuid(&self) -> &Uuid {
+        self.id.as_uuid()
+    }
+
+    /// Returns a slice of 16 octets containing the value.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8; 16] {
+        self.id.as_bytes()
+    }
+
+    /// Tests if this ID is all zeros.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.id.is_empty()
+    }
+}
+
+/// Parses `value` as `{prefix}_{id}`, rejecting an empty `id` rather than reading it as the nil ID.
+pub fn parse_prefixed(value: &str, prefix: &'static str) -> Result<ShortGuid, ParseError> {
+    let id = value
+        .strip_prefix(prefix)
+        .and_then(|rest| rest.strip_prefix('_'))
+        .ok_or(ParseError::InvalidPrefix { expected: prefix })?;
+    ShortGuid::try_parse_non_empty(id)
+}
+
+/// Formats `id` as `{prefix}_{id}`.
+pub fn fmt_prefixed(f: &mut Formatter<'_>, prefix: &str, id: &ShortGuid) -> std::fmt::Result {
+    write!(
+        f,
+        "{prefix}_{short}",
+        short = ShortGuid::encode(id.as_uuid())
+    )
+}
+
+impl<T: IdPrefix> Debug for TypedShortGuid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        fmt_prefixed(f, T::PREFIX, &self.id)?;
+        write!(f, " ({long})", long = self.id.as_uuid())
+    }
+}
+
+impl<T: IdPrefix> Display for TypedShortGuid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        fmt_prefixed(f, T::PREFIX, &self.id)
+    }
+}
+
+impl<T: IdPrefix> Clone for TypedShortGuid<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: IdPrefix> Copy for TypedShortGuid<T> {}
+
+impl<T: IdPrefix> PartialEq for TypedShortGuid<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id.eq(&other.id)
+    }
+}
+
+impl<T: IdPrefix> Eq for TypedShortGuid<T> {}
+
+impl<T: IdPrefix> PartialOrd for TypedShortGuid<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: IdPrefix> Ord for TypedShortGuid<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.id.cmp(&other.id)
+    }
+}
+
+impl<T: IdPrefix> Hash for TypedShortGuid<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state)
+    }
+}
+
+impl<T: IdPrefix> From<TypedShortGuid<T>> for ShortGuid {
+    fn from(value: TypedShortGuid<T>) -> Self {
+        value.id
+    }
+}
+
+impl<T: IdPrefix> From<TypedShortGuid<T>> for Uuid {
+    fn from(value: TypedShortGuid<T>) -> Self {
+        value.id.into()
+    }
+}
+
+impl<T: IdPrefix> TryFrom<String> for TypedShortGuid<T> {
+    type Error = ParseError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::try_parse(value)
+    }
+}
+
+impl<T: IdPrefix> TryFrom<&str> for TypedShortGuid<T> {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::try_parse(value)
+    }
+}
+
+impl<T: IdPrefix> FromStr for TypedShortGuid<T> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_parse(s)
+    }
+}
+
+impl<T: IdPrefix> AsRef<ShortGuid> for TypedShortGuid<T> {
+    fn as_ref(&self) -> &ShortGuid {
+        &self.id
+    }
+}
+
+impl<T: IdPrefix> AsRef<Uuid> for TypedShortGuid<T> {
+    fn as_ref(&self) -> &Uuid {
+        self.as_uuid()
+    }
+}
+
+impl<T: IdPrefix> AsRef<[u8]> for TypedShortGuid<T> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+// SAFETY: `TypedShortGuid` is a transparent wrapper around `ShortGuid`,
+// the marker is zero-sized and carries no data.
+#[cfg(feature = "bytemuck")]
+unsafe impl<T: IdPrefix> bytemuck::Zeroable for TypedShortGuid<T> {}
+
+// SAFETY: See above; `ShortGuid` is `Pod`.
+#[cfg(feature = "bytemuck")]
+unsafe impl<T: IdPrefix + 'static> bytemuck::Pod for TypedShortGuid<T> {}
+
+// SAFETY: `TypedShortGuid` is `#[repr(transparent)]` over `ShortGuid`.
+#[cfg(feature = "bytemuck")]
+unsafe impl<T: IdPrefix> bytemuck::TransparentWrapper<ShortGuid> for TypedShortGuid<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::hash_map::DefaultHasher;
+
+    enum User {}
+
+    impl IdPrefix for User {
+        const PREFIX: &'static str = "user";
+    }
+
+    enum Order {}
+
+    impl IdPrefix for Order {
+        const PREFIX: &'static str = "order";
+    }
+
+    #[test]
+    fn display_works() {
+        let id = TypedShortGuid::<User>::new(
+            ShortGuid::try_parse("c9a646d3-9c61-4cb7-bfcd-ee2522c8f633").unwrap(),
+        );
+        assert_eq!(id.to_string(), "user_yaZG05xhTLe_ze4lIsj2Mw");
+        assert_eq!(
+            format!("{id:?}"),
+            "user_yaZG05xhTLe_ze4lIsj2Mw (c9a646d3-9c61-4cb7-bfcd-ee2522c8f633)"
+        );
+    }
+
+    #[test]
+    fn try_parse_works() {
+        let id = TypedShortGuid::<User>::try_parse("user_yaZG05xhTLe_ze4lIsj2Mw").unwrap();
+        assert_eq!(id.as_short_guid(), &"yaZG05xhTLe_ze4lIsj2Mw");
+
+        let id =
+            TypedShortGuid::<User>::try_parse("user_c9a646d3-9c61-4cb7-bfcd-ee2522c8f633").unwrap();
+        assert_eq!(id.into_short_guid(), "yaZG05xhTLe_ze4lIsj2Mw");
+    }
+
+    #[test]
+    fn try_parse_with_wrong_prefix_fails() {
+        assert_eq!(
+            TypedShortGuid::<Order>::try_parse("user_yaZG05xhTLe_ze4lIsj2Mw").unwrap_err(),
+            ParseError::InvalidPrefix { expected: "order" }
+        );
+        assert_eq!(
+            TypedShortGuid::<User>::try_parse("yaZG05xhTLe_ze4lIsj2Mw").unwrap_err(),
+            ParseError::InvalidPrefix { expected: "user" }
+        );
+        assert_eq!(
+            TypedShortGuid::<User>::try_parse("useryaZG05xhTLe_ze4lIsj2Mw").unwrap_err(),
+            ParseError::InvalidPrefix { expected: "user" }
+        );
+    }
+
+    #[test]
+    fn try_parse_with_invalid_id_fails() {
+        assert!(matches!(
+            TypedShortGuid::<User>::try_parse("user_yaZG05xh").unwrap_err(),
+            ParseError::InvalidLength(..)
+        ));
+    }
+
+    #[test]
+    fn try_parse_without_id_fails() {
+        assert_eq!(
+            TypedShortGuid::<User>::try_parse("user_").unwrap_err(),
+            ParseError::InvalidLength(0)
+        );
+        assert_eq!(
+            TypedShortGuid::<User>::try_parse("user").unwrap_err(),
+            ParseError::InvalidPrefix { expected: "user" }
+        );
+    }
+
+    #[test]
+    fn ordering_and_hash_match_inner() {
+        let a = ShortGuid::try_parse("00000000-0000-0000-0000-000000000001").unwrap();
+        let b = ShortGuid::try_parse("00000000-0000-0000-0000-000000000002").unwrap();
+        assert!(TypedShortGuid::<User>::new(a) < TypedShortGuid::<User>::new(b));
+
+        let mut typed = DefaultHasher::new();
+        TypedShortGuid::<User>::new(a).hash(&mut typed);
+        let mut plain = DefaultHasher::new();
+        a.hash(&mut plain);
+        assert_eq!(typed.finish(), plain.finish());
+    }
+
+    #[test]
+    fn layout_matches_inner() {
+        assert_eq!(
+            std::mem::size_of::<TypedShortGuid<User>>(),
+            std::mem::size_of::<ShortGuid>()
+        );
+        assert_eq!(
+            std::mem::align_of::<TypedShortGuid<User>>(),
+            std::mem::align_of::<ShortGuid>()
+        );
+    }
+}
@@ -0,0 +1,365 @@
+use crate::{ParseError, ShortGuid};
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+use std::str::FromStr;
+
+/// The Crockford base32 alphabet used by TypeID, in lowercase.
+const ALPHABET: &[u8; 32] = b"0123456789abcdefghjkmnpqrstvwxyz";
+
+/// The maximum length of a TypeID prefix.
+const MAX_PREFIX_LEN: usize = 63;
+
+/// The length of an encoded TypeID suffix.
+const SUFFIX_LEN: usize = 26;
+
+/// A [TypeID](https://github.com/jetify-com/typeid), i.e. a type prefix followed by
+/// a Crockford base32 encoded UUID, e.g. `user_01h455vb4pex5vsknk084sn02q`.
+///
+/// ## Example
+///
+/// ```
+/// # use shortguid::{ShortGuid, TypeId};
+/// let type_id = TypeId::try_parse("user_01h455vb4pex5vsknk084sn02q").unwrap();
+/// assert_eq!(type_id.prefix(), "user");
+/// assert_eq!(type_id.id(), "01890a5d-ac96-774b-bcce-b302099a8057");
+/// assert_eq!(type_id.to_string(), "user_01h455vb4pex5vsknk084sn02q");
+///
+/// let id = ShortGuid::try_parse("01890a5d-ac96-774b-bcce-b302099a8057").unwrap();
+/// assert_eq!(TypeId::new("user", id).unwrap(), type_id);
+/// ```
+#[derive(Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct TypeId {
+    prefix: String,
+    id: ShortGuid,
+}
+
+impl TypeId {
+    /// Creates a new [`TypeId`] from the given prefix and ID.
+    ///
+    /// The prefix must consist of at most 63 lowercase ASCII letters and underscores,
+    /// and must neither start nor end with an underscore. It may be empty.
+    pub fn new<P: Into<String>>(prefix: P, id: ShortGuid) -> Result<Self, ParseError> {
+        let prefix = prefix.into();
+        validate_prefix(&prefix)?;
+        Ok(Self { prefix, id })
+    }
+
+    /// Tries to parse the value as a TypeID string.
+    pub fn try_parse<S: AsRef<str>>(value: S) -> Result<Self, ParseError> {
+        let value = value.as_ref();
+        let (prefix, suffix) = match value.rsplit_once('_') {
+            Some(("", _)) => return Err(TypeIdError::EmptyPrefix.into()),
+            Some((prefix, suffix)) => (prefix, suffix),
+            None => ("", value),
+        };
+
+        validate_prefix(prefix)?;
+        let id = ShortGuid::from_bytes(decode_suffix(suffix)?);
+        Ok(Self {
+            prefix: prefix.to_string(),
+            id,
+        })
+    }
+
+    /// Returns the type prefix, without the separator.
+    #[inline]
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Returns the ID.
+    #[inline]
+    pub const fn id(&self) -> &ShortGuid {
+        &self.id
+    }
+
+    /// Splits this [`TypeId`] into its prefix and ID.
+    #[inline]
+    pub fn into_parts(self) -> (String, ShortGuid) {
+        (self.prefix, self.id)
+    }
+}
+
+impl ShortGuid {
+    /// Encodes this [`ShortGuid`] as a 26 character TypeID suffix, i.e. a TypeID
+    /// without a prefix.
+    ///
+    /// ```
+    /// # use shortguid::ShortGuid;
+    /// let id = ShortGuid::try_parse("01890a5d-ac96-774b-bcce-b302099a8057").unwrap();
+    /// assert_eq!(id.to_typeid_suffix(), "01h455vb4pex5vsknk084sn02q");
+    /// ```
+    pub fn to_typeid_suffix(&self) -> String {
+        let value = self.as_uuid().as_u128();
+        (0..SUFFIX_LEN)
+            .rev()
+            .map(|index| ALPHABET[((value >> (5 * index)) & 0x1f) as usize] as char)
+            .collect()
+    }
+}
+
+/// Validates the TypeID prefix grammar.
+fn validate_prefix(prefix: &str) -> Result<(), TypeIdError> {
+    if prefix.len() > MAX_PREFIX_LEN {
+        return Err(TypeIdError::PrefixTooLong(prefix.len()));
+    }
+
+    let valid = prefix.bytes().all(|c| c.is_ascii_lowercase() || c == b'_')
+        && !prefix.starts_with('_')
+        && !prefix.ends_with('_');
+    if !valid {
+        return Err(TypeIdError::InvalidPrefix);
+    }
+
+    Ok(())
+}
+
+/// Decodes a 26 character TypeID suffix.
+fn decode_suffix(suffix: &str) -> Result<[u8; 16], TypeIdError> {
+    if suffix.len() != SUFFIX_LEN {
+        return Err(TypeIdError::InvalidSuffixLength(suffix.len()));
+    }
+
+    let mut value = 0u128;
+    for c in suffix.chars() {
+        let digit = ALPHABET
+            .iter()
+            .position(|&a| a as char == c)
+            .ok_or(TypeIdError::InvalidSuffixCharacter(c))?;
+        value = (value << 5) | digit as u128;
+    }
+
+    // 26 characters carry 130 bits; the first character may only hold three of them.
+    if !matches!(suffix.as_bytes()[0], b'0'..=b'7') {
+        return Err(TypeIdError::SuffixOverflow);
+    }
+
+    Ok(value.to_be_bytes())
+}
+
+impl Debug for TypeId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self} ({long})", long = self.id.as_uuid())
+    }
+}
+
+impl Display for TypeId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if !self.prefix.is_empty() {
+            write!(f, "{}_", self.prefix)?;
+        }
+
+        write!(f, "{}", self.id.to_typeid_suffix())
+    }
+}
+
+impl From<TypeId> for ShortGuid {
+    fn from(value: TypeId) -> Self {
+        value.id
+    }
+}
+
+impl TryFrom<String> for TypeId {
+    type Error = ParseError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        TypeId::try_parse(value)
+    }
+}
+
+impl TryFrom<&str> for TypeId {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        TypeId::try_parse(value)
+    }
+}
+
+impl FromStr for TypeId {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        TypeId::try_parse(s)
+    }
+}
+
+/// A TypeID specific parsing error.
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum TypeIdError {
+    /// The prefix was longer than 63 characters.
+    /// The contained value is the actual size.
+    PrefixTooLong(usize),
+    /// The prefix contained characters other than lowercase ASCII letters and underscores,
+    /// or started or ended with an underscore.
+    InvalidPrefix,
+    /// The input contained a separator, but no prefix.
+    EmptyPrefix,
+    /// The suffix had an invalid length.
+    /// The contained value is the actual size.
+    InvalidSuffixLength(usize),
+    /// The suffix contained a character outside the lowercase Crockford base32 alphabet.
+    InvalidSuffixCharacter(char),
+    /// The suffix encoded a value larger than 128 bits.
+    SuffixOverflow,
+}
+
+impl From<TypeIdError> for ParseError {
+    fn from(value: TypeIdError) -> Self {
+        Self::InvalidTypeId(value)
+    }
+}
+
+impl Debug for TypeIdError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl Display for TypeIdError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TypeIdError::PrefixTooLong(len) => write!(
+                f,
+                "prefix too long; expected at most {MAX_PREFIX_LEN} characters, but got {len}"
+            ),
+            TypeIdError::InvalidPrefix => write!(
+                f,
+                "prefix must only contain lowercase letters and inner underscores"
+            ),
+            TypeIdError::EmptyPrefix => write!(f, "separator must be omitted for an empty prefix"),
+            TypeIdError::InvalidSuffixLength(len) => write!(
+                f,
+                "invalid suffix length; expected {SUFFIX_LEN} characters, but got {len}"
+            ),
+            TypeIdError::InvalidSuffixCharacter(c) => {
+                write!(f, "invalid suffix character {c:?}")
+            }
+            TypeIdError::SuffixOverflow => write!(f, "suffix exceeds 128 bits"),
+        }
+    }
+}
+
+impl Error for TypeIdError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The valid test vectors of the TypeID specification, as `(typeid, prefix, uuid)`.
+    const VALID: &[(&str, &str, &str)] = &[
+        (
+            "00000000000000000000000000",
+            "",
+            "00000000-0000-0000-0000-000000000000",
+        ),
+        (
+            "00000000000000000000000001",
+            "",
+            "00000000-0000-0000-0000-000000000001",
+        ),
+        (
+            "0000000000000000000000000a",
+            "",
+            "00000000-0000-0000-0000-00000000000a",
+        ),
+        (
+            "0000000000000000000000000g",
+            "",
+            "00000000-0000-0000-0000-000000000010",
+        ),
+        (
+            "00000000000000000000000010",
+            "",
+            "00000000-0000-0000-0000-000000000020",
+        ),
+        (
+            "7zzzzzzzzzzzzzzzzzzzzzzzzz",
+            "",
+            "ffffffff-ffff-ffff-ffff-ffffffffffff",
+        ),
+        (
+            "prefix_0123456789abcdefghjkmnpqrs",
+            "prefix",
+            "0110c853-1d09-52d8-d73e-1194e95b5f19",
+        ),
+        (
+            "prefix_01h455vb4pex5vsknk084sn02q",
+            "prefix",
+            "01890a5d-ac96-774b-bcce-b302099a8057",
+        ),
+        (
+            "pre_fix_00000000000000000000000000",
+            "pre_fix",
+            "00000000-0000-0000-0000-000000000000",
+        ),
+    ];
+
+    /// The invalid test vectors of the TypeID specification.
+    const INVALID: &[&str] = &[
+        "PREFIX_00000000000000000000000000",
+        "12345_00000000000000000000000000",
+        "pre.fix_00000000000000000000000000",
+        "préfix_00000000000000000000000000",
+        "  prefix_00000000000000000000000000",
+        "abcdefghijklmnopqrstuvwxyzabcdefghijklmnopqrstuvwxyzabcdefghijkl_00000000000000000000000000",
+        "_00000000000000000000000000",
+        "_",
+        "prefix_1234567890123456789012345",
+        "prefix_123456789012345678901234567",
+        "prefix_1234567890123456789012345 ",
+        "prefix_0123456789ABCDEFGHJKMNPQRS",
+        "prefix_123456789-123456789-123456",
+        "prefix_ooooooiiiiiiuuuuuuulllllll",
+        "prefix_i23456789ol23456789oi23456",
+        "prefix_123456789-0123456789-0123456",
+        "prefix_8zzzzzzzzzzzzzzzzzzzzzzzzz",
+        "_prefix_00000000000000000000000000",
+        "prefix__00000000000000000000000000",
+        "",
+    ];
+
+    #[test]
+    fn valid_vectors_roundtrip() {
+        for &(typeid, prefix, uuid) in VALID {
+            let parsed = TypeId::try_parse(typeid).unwrap();
+            assert_eq!(parsed.prefix(), prefix, "{typeid}");
+            assert_eq!(parsed.id(), &uuid, "{typeid}");
+
+            let id = ShortGuid::try_parse(uuid).unwrap();
+            assert_eq!(TypeId::new(prefix, id).unwrap().to_string(), typeid);
+        }
+    }
+
+    #[test]
+    fn invalid_vectors_fail() {
+        for &typeid in INVALID {
+            assert!(
+                matches!(
+                    TypeId::try_parse(typeid),
+                    Err(ParseError::InvalidTypeId(..))
+                ),
+                "{typeid:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn new_with_invalid_prefix_fails() {
+        assert_eq!(
+            TypeId::new("User", ShortGuid::default()).unwrap_err(),
+            ParseError::InvalidTypeId(TypeIdError::InvalidPrefix)
+        );
+        assert_eq!(
+            TypeId::new("a".repeat(64), ShortGuid::default()).unwrap_err(),
+            ParseError::InvalidTypeId(TypeIdError::PrefixTooLong(64))
+        );
+    }
+
+    #[test]
+    fn overflow_is_detected() {
+        assert_eq!(
+            TypeId::try_parse("8zzzzzzzzzzzzzzzzzzzzzzzzz").unwrap_err(),
+            ParseError::InvalidTypeId(TypeIdError::SuffixOverflow)
+        );
+    }
+}
@@ -0,0 +1,407 @@
+use crate::{ParseError, ShortGuid};
+use std::fmt::{Debug, Display, Formatter};
+use std::str::FromStr;
+use uuid::{Uuid, Variant};
+
+/// A [`ShortGuid`] that is guaranteed to be a UUID v4.
+pub type ShortGuidV4 = VersionedShortGuid<4>;
+
+/// A [`ShortGuid`] that is guaranteed to be a UUID v7.
+pub type ShortGuidV7 = VersionedShortGuid<7>;
+
+/// A runtime policy restricting the UUID versions accepted when parsing a [`ShortGuid`].
+///
+/// Only values of the RFC 9562 (RFC 4122) variant are accepted, since the version of
+/// other variants is meaningless. The nil (all zeros) and max (all ones) values are
+/// rejected unless explicitly allowed.
+///
+/// ## Example
+///
+/// ```
+/// # use shortguid::{ParseError, ShortGuid, VersionPolicy};
+/// let policy = VersionPolicy::only(4).allow(7);
+///
+/// let id = ShortGuid::try_parse_with_policy("c9a646d3-9c61-4cb7-bfcd-ee2522c8f633", &policy);
+/// assert!(id.is_ok());
+///
+/// let nil = ShortGuid::try_parse_with_policy("AAAAAAAAAAAAAAAAAAAAAA", &policy);
+/// assert_eq!(nil.unwrap_err(), ParseError::NilValue);
+///
+/// let v1 = ShortGuid::try_parse_with_policy("6ba7b810-9dad-11d1-80b4-00c04fd430c8", &policy);
+/// assert_eq!(v1.unwrap_err(), ParseError::InvalidVersion(1));
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct VersionPolicy {
+    /// A bit mask of the accepted versions, where bit `n` represents version `n`.
+    versions: u16,
+    allow_nil: bool,
+    allow_max: bool,
+    allow_any_variant: bool,
+}
+
+impl VersionPolicy {
+    /// A policy accepting every value, including nil, max and other variants.
+    pub const ANY: Self = Self {
+        versions: u16::MAX,
+        allow_nil: true,
+        allow_max: true,
+        allow_any_variant: true,
+    };
+
+    /// Creates a policy that only accepts the specified UUID version.
+    ///
+    /// ## Panics
+    /// Panics if `version` is greater than 15.
+    #[inline]
+    pub const fn only(version: usize) -> Self {
+        Self {
+            versions: 0,
+            allow_nil: false,
+            allow_max: false,
+            allow_any_variant: false,
+        }
+        .allow(version)
+    }
+
+    /// Additionally accepts the specified UUID version.
+    ///
+    /// ## Panics
+    /// Panics if `version` is greater than 15.
+    #[inline]
+    pub const fn allow(mut self, version: usize) -> Self {
+        assert!(version < 16, "UUID versions are in the range 0 to 15");
+        self.versions |= 1 << version;
+        self
+    }
+
+    /// Sets whether the nil (all zeros) value is accepted.
+    #[inline]
+    pub const fn allow_nil(mut self, allow: bool) -> Self {
+        self.allow_nil = allow;
+        self
+    }
+
+    /// Sets whether the max (all ones) value is accepted.
+    #[inline]
+    pub const fn allow_max(mut self, allow: bool) -> Self {
+        self.allow_max = allow;
+        self
+    }
+
+    /// Sets whether values of variants other than RFC 9562 (RFC 4122) are accepted,
+    /// in which case only their version bits are checked.
+    #[inline]
+    pub const fn allow_any_variant(mut self, allow: bool) -> Self {
+        self.allow_any_variant = allow;
+        self
+    }
+
+    /// Tests whether the provided [`ShortGuid`] is accepted by this policy.
+    pub fn check(&self, id: &ShortGuid) -> Result<(), ParseError> {
+        if id.is_empty() {
+            return if self.allow_nil {
+                Ok(())
+            } else {
+                Err(ParseError::NilValue)
+            };
+        }
+
+        if id.as_bytes() == &[0xff; 16] {
+            return if self.allow_max {
+                Ok(())
+            } else {
+                Err(ParseError::MaxValue)
+            };
+        }
+
+        let variant = id.as_uuid().get_variant();
+        if !self.allow_any_variant && variant != Variant::RFC4122 {
+            return Err(ParseError::InvalidVariant(variant));
+        }
+
+        let version = id.as_uuid().get_version_num();
+        if self.versions & (1 << version) == 0 {
+            return Err(ParseError::InvalidVersion(version));
+        }
+
+        Ok(())
+    }
+}
+
+impl ShortGuid {
+    /// Tries to parse the value like [`ShortGuid::try_parse`], and additionally
+    /// verifies it against the provided [`VersionPolicy`].
+    pub fn try_parse_with_policy<S: AsRef<str>>(
+        value: S,
+        policy: &VersionPolicy,
+    ) -> Result<Self, ParseError> {
+        let id = Self::try_parse(value)?;
+        policy.check(&id)?;
+        Ok(id)
+    }
+}
+
+/// A [`ShortGuid`] that is guaranteed to be of the UUID version `VERSION`.
+///
+/// Nil and max values are rejected. Use the [`ShortGuidV4`] and [`ShortGuidV7`]
+/// aliases for the common versions.
+///
+/// ## Example
+///
+/// ```
+/// # use shortguid::{ParseError, ShortGuid, ShortGuidV4};
+/// let id = ShortGuidV4::try_parse("yaZG05xhTLe_ze4lIsj2Mw").unwrap();
+/// let id: ShortGuid = id.into_short_guid();
+///
+/// assert_eq!(
+///     ShortGuidV4::try_parse("AAAAAAAAAAAAAAAAAAAAAA").unwrap_err(),
+///     ParseError::NilValue
+/// );
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[repr(transparent)]
+pub struct VersionedShortGuid<const VERSION: usize>(ShortGuid);
+
+impl ShortGuidV4 {
+    /// Generates a new [`ShortGuidV4`] based on a random UUID v4.
+    #[cfg_attr(docsrs, doc(cfg(feature = "random")))]
+    #[cfg(feature = "random")]
+    #[inline(always)]
+    pub fn new_random() -> Self {
+        Self(ShortGuid::new_random())
+    }
+}
+
+impl<const VERSION: usize> VersionedShortGuid<VERSION> {
+    /// The policy enforced by this type.
+    const POLICY: VersionPolicy = VersionPolicy::only(VERSION);
+
+    /// Creates a new instance if the provided [`ShortGuid`] is of the expected version.
+    pub fn new(id: ShortGuid) -> Result<Self, ParseError> {
+        Self::POLICY.check(&id)?;
+        Ok(Self(id))
+    }
+
+    /// Tries to parse the value like [`ShortGuid::try_parse`], rejecting IDs of other versions.
+    pub fn try_parse<S: AsRef<str>>(value: S) -> Result<Self, ParseError> {
+        ShortGuid::try_parse_with_policy(value, &Self::POLICY).map(Self)
+    }
+
+    /// Returns the underlying [`ShortGuid`] instance.
+    #[inline]
+    pub const fn as_short_guid(&self) -> &ShortGuid {
+        &self.0
+    }
+
+    /// Converts this value back into a general [`ShortGuid`].
+    #[inline]
+    pub const fn into_short_guid(self) -> ShortGuid {
+        self.0
+    }
+
+    /// Returns the underlying [`Uuid`] instance.
+    #[inline]
+    pub const fn as_uuid(&self) -> &Uuid {
+        self.0.as_uuid()
+    }
+
+    /// Returns a slice of 16 octets containing the value.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8; 16] {
+        self.0.as_bytes()
+    }
+}
+
+impl<const VERSION: usize> Debug for VersionedShortGuid<VERSION> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+impl<const VERSION: usize> Display for VersionedShortGuid<VERSION> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl<const VERSION: usize> TryFrom<ShortGuid> for VersionedShortGuid<VERSION> {
+    type Error = ParseError;
+
+    fn try_from(value: ShortGuid) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl<const VERSION: usize> TryFrom<Uuid> for VersionedShortGuid<VERSION> {
+    type Error = ParseError;
+
+    fn try_from(value: Uuid) -> Result<Self, Self::Error> {
+        Self::new(ShortGuid::from(value))
+    }
+}
+
+impl<const VERSION: usize> TryFrom<String> for VersionedShortGuid<VERSION> {
+    type Error = ParseError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::try_parse(value)
+    }
+}
+
+impl<const VERSION: usize> TryFrom<&str> for VersionedShortGuid<VERSION> {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::try_parse(value)
+    }
+}
+
+impl<const VERSION: usize> FromStr for VersionedShortGuid<VERSION> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_parse(s)
+    }
+}
+
+impl<const VERSION: usize> From<VersionedShortGuid<VERSION>> for ShortGuid {
+    fn from(value: VersionedShortGuid<VERSION>) -> Self {
+        value.0
+    }
+}
+
+impl<const VERSION: usize> From<VersionedShortGuid<VERSION>> for Uuid {
+    fn from(value: VersionedShortGuid<VERSION>) -> Self {
+        value.0.into()
+    }
+}
+
+impl<const VERSION: usize> PartialEq<ShortGuid> for VersionedShortGuid<VERSION> {
+    fn eq(&self, other: &ShortGuid) -> bool {
+        self.0.eq(other)
+    }
+}
+
+impl<const VERSION: usize> PartialEq<str> for VersionedShortGuid<VERSION> {
+    fn eq(&self, other: &str) -> bool {
+        self.0.eq(other)
+    }
+}
+
+impl<const VERSION: usize> PartialEq<&str> for VersionedShortGuid<VERSION> {
+    fn eq(&self, other: &&str) -> bool {
+        self.0.eq(*other)
+    }
+}
+
+impl<const VERSION: usize> AsRef<ShortGuid> for VersionedShortGuid<VERSION> {
+    fn as_ref(&self) -> &ShortGuid {
+        &self.0
+    }
+}
+
+impl<const VERSION: usize> AsRef<Uuid> for VersionedShortGuid<VERSION> {
+    fn as_ref(&self) -> &Uuid {
+        self.as_uuid()
+    }
+}
+
+impl<const VERSION: usize> AsRef<[u8]> for VersionedShortGuid<VERSION> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const V4: &str = "c9a646d3-9c61-4cb7-bfcd-ee2522c8f633";
+    const V7: &str = "01890a5d-ac96-774b-bcce-b302099a8057";
+    const V1: &str = "a1a2a3a4-b1b2-11c2-91d2-d3d4d5d6d7d8";
+    /// A v4 version nibble, but of the Microsoft variant.
+    const V4_MICROSOFT: &str = "c9a646d3-9c61-4cb7-dfcd-ee2522c8f633";
+    /// A v4 version nibble, but of the NCS variant.
+    const V4_NCS: &str = "c9a646d3-9c61-4cb7-3fcd-ee2522c8f633";
+    const MAX: &str = "ffffffff-ffff-ffff-ffff-ffffffffffff";
+
+    #[test]
+    fn versioned_try_parse_works() {
+        assert_eq!(
+            ShortGuidV4::try_parse(V4).unwrap(),
+            "yaZG05xhTLe_ze4lIsj2Mw"
+        );
+        assert_eq!(ShortGuidV7::try_parse(V7).unwrap(), V7);
+    }
+
+    #[test]
+    fn versioned_try_parse_with_wrong_version_fails() {
+        assert_eq!(
+            ShortGuidV4::try_parse(V7).unwrap_err(),
+            ParseError::InvalidVersion(7)
+        );
+        assert_eq!(
+            ShortGuidV7::try_parse(V1).unwrap_err(),
+            ParseError::InvalidVersion(1)
+        );
+        assert_eq!(
+            ShortGuidV7::try_parse("AAAAAAAAAAAAAAAAAAAAAA").unwrap_err(),
+            ParseError::NilValue
+        );
+        assert_eq!(
+            ShortGuidV4::try_parse(MAX).unwrap_err(),
+            ParseError::MaxValue
+        );
+    }
+
+    #[test]
+    fn versioned_try_parse_with_wrong_variant_fails() {
+        assert_eq!(
+            ShortGuidV4::try_parse(V4_MICROSOFT).unwrap_err(),
+            ParseError::InvalidVariant(Variant::Microsoft)
+        );
+        assert_eq!(
+            ShortGuidV4::try_parse(V4_NCS).unwrap_err(),
+            ParseError::InvalidVariant(Variant::NCS)
+        );
+
+        let policy = VersionPolicy::only(4).allow_any_variant(true);
+        assert!(ShortGuid::try_parse_with_policy(V4_MICROSOFT, &policy).is_ok());
+    }
+
+    #[test]
+    fn versioned_converts_back() {
+        let id = ShortGuid::try_parse(V4).unwrap();
+        let versioned = ShortGuidV4::try_from(id).unwrap();
+        assert_eq!(versioned.into_short_guid(), id);
+        assert_eq!(ShortGuid::from(versioned), id);
+    }
+
+    #[test]
+    fn new_random_is_v4() {
+        let id = ShortGuidV4::new_random();
+        assert_eq!(id.as_uuid().get_version_num(), 4);
+    }
+
+    #[test]
+    fn policy_works() {
+        let policy = VersionPolicy::only(4).allow(7);
+        assert!(ShortGuid::try_parse_with_policy(V4, &policy).is_ok());
+        assert!(ShortGuid::try_parse_with_policy(V7, &policy).is_ok());
+        assert_eq!(
+            ShortGuid::try_parse_with_policy(V1, &policy).unwrap_err(),
+            ParseError::InvalidVersion(1)
+        );
+
+        let policy = policy.allow_nil(true).allow_max(true);
+        assert!(ShortGuid::try_parse_with_policy("AAAAAAAAAAAAAAAAAAAAAA", &policy).is_ok());
+        assert!(ShortGuid::try_parse_with_policy(MAX, &policy).is_ok());
+    }
+
+    #[test]
+    fn policy_any_accepts_everything() {
+        for value in [V4, V7, V1, V4_MICROSOFT, MAX, "AAAAAAAAAAAAAAAAAAAAAA"] {
+            assert!(ShortGuid::try_parse_with_policy(value, &VersionPolicy::ANY).is_ok());
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use shortguid::{ParseError, ShortGuid, ShortGuidNewtype};
+use uuid::Uuid;
+
+#[derive(ShortGuidNewtype, Copy, Clone, Eq, PartialEq, Hash)]
+struct OrderId(ShortGuid);
+
+#[derive(ShortGuidNewtype, Copy, Clone, Eq, PartialEq, Hash)]
+#[shortguid(prefix = "user")]
+struct UserId {
+    id: ShortGuid,
+}
+
+const UUID: &str = "c9a646d3-9c61-4cb7-bfcd-ee2522c8f633";
+const SHORT: &str = "yaZG05xhTLe_ze4lIsj2Mw";
+const BYTES: [u8; 16] = [
+    0xc9, 0xa6, 0x46, 0xd3, 0x9c, 0x61, 0x4c, 0xb7, 0xbf, 0xcd, 0xee, 0x25, 0x22, 0xc8, 0xf6, 0x33,
+];
+
+#[test]
+fn display_and_debug_forward_to_inner() {
+    let id = OrderId(ShortGuid::try_parse(UUID).unwrap());
+    assert_eq!(id.to_string(), SHORT);
+    assert_eq!(format!("{id:?}"), format!("OrderId({SHORT} ({UUID}))"));
+}
+
+#[test]
+fn from_str_works() {
+    let id: OrderId = SHORT.parse().unwrap();
+    assert_eq!(id, ShortGuid::try_parse(UUID).unwrap());
+    assert_eq!(OrderId::try_from(UUID).unwrap(), id);
+    assert_eq!(OrderId::try_from(UUID.to_string()).unwrap(), id);
+}
+
+#[test]
+fn conversions_work() {
+    let uuid = Uuid::try_parse(UUID).unwrap();
+    let id = OrderId::from(uuid);
+    assert_eq!(Uuid::from(id), uuid);
+    assert_eq!(ShortGuid::from(id), ShortGuid::from(uuid));
+    assert_eq!(OrderId::from(ShortGuid::from(uuid)), id);
+    assert_eq!(AsRef::<Uuid>::as_ref(&id), &uuid);
+    assert_eq!(AsRef::<[u8]>::as_ref(&id), uuid.as_bytes());
+}
+
+#[test]
+fn eq_works() {
+    let id = OrderId::from(Uuid::try_parse(UUID).unwrap());
+    assert_eq!(id, SHORT);
+    assert_eq!(id, *UUID);
+    assert_eq!(id, SHORT.to_string());
+    assert_eq!(id, BYTES);
+    assert_ne!(id, "AAAAAAAAAAAAAAAAAAAAAA");
+}
+
+#[test]
+fn prefix_works() {
+    let id: UserId = format!("user_{SHORT}").parse().unwrap();
+    assert_eq!(id.id, ShortGuid::try_parse(UUID).unwrap());
+    assert_eq!(id.to_string(), format!("user_{SHORT}"));
+    assert_eq!(id, format!("user_{SHORT}"));
+    assert_ne!(id, SHORT);
+
+    assert_eq!(
+        SHORT.parse::<UserId>().unwrap_err(),
+        ParseError::InvalidPrefix { expected: "user" }
+    );
+}
+
+#[cfg(feature = "serde")]
+mod serde {
+    use super::*;
+    use serde_test::{Compact, Configure, Readable, Token};
+
+    #[derive(ShortGuidNewtype, Copy, Clone, Eq, PartialEq)]
+    #[shortguid(serde)]
+    struct PlainId(ShortGuid);
+
+    #[derive(ShortGuidNewtype, Copy, Clone, Eq, PartialEq)]
+    #[shortguid(prefix = "user", serde)]
+    struct PrefixedId(ShortGuid);
+
+    #[test]
+    fn test_serialize_plain() {
+        let id = PlainId::from(Uuid::try_parse(UUID).unwrap());
+        serde_test::assert_tokens(&id.readable(), &[Token::Str(SHORT)]);
+        serde_test::assert_tokens(&id.compact(), &[Token::Bytes(&BYTES)]);
+    }
+
+    #[test]
+    fn test_serialize_prefixed() {
+        let id = PrefixedId::from(Uuid::try_parse(UUID).unwrap());
+        serde_test::assert_tokens(&id.readable(), &[Token::Str("user_yaZG05xhTLe_ze4lIsj2Mw")]);
+        serde_test::assert_tokens(&id.compact(), &[Token::Bytes(&BYTES)]);
+    }
+
+    #[test]
+    fn test_de_failure() {
+        serde_test::assert_de_tokens_error::<Readable<PrefixedId>>(
+            &[Token::Str(SHORT)],
+            "ShortGuid parsing failed: Invalid ID prefix; expected \"user_\"",
+        );
+        serde_test::assert_de_tokens_error::<Compact<PlainId>>(
+            &[Token::Bytes(b"hello_world")],
+            "UUID parsing failed: invalid length: expected 16 bytes, found 11",
+        );
+    }
+}
+
+#[cfg(feature = "borsh")]
+mod borsh {
+    use super::*;
+
+    #[derive(ShortGuidNewtype, Copy, Clone, Eq, PartialEq)]
+    #[shortguid(borsh)]
+    struct BorshId(ShortGuid);
+
+    #[test]
+    fn test_roundtrip() {
+        let id = BorshId::from(Uuid::try_parse(UUID).unwrap());
+        let bytes = ::borsh::to_vec(&id).unwrap();
+        assert_eq!(bytes, BYTES);
+        assert_eq!(::borsh::from_slice::<BorshId>(&bytes).unwrap(), id);
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+mod bytemuck {
+    use super::*;
+
+    #[derive(ShortGuidNewtype, Copy, Clone, Eq, PartialEq)]
+    #[shortguid(bytemuck)]
+    #[repr(transparent)]
+    struct PodId(ShortGuid);
+
+    #[test]
+    fn test_cast() {
+        let id: PodId = ::bytemuck::cast(BYTES);
+        assert_eq!(id, SHORT);
+    }
+}
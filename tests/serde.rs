@@ -79,3 +79,864 @@ fn test_de_failure() {
         "UUID parsing failed: invalid length: expected 16 bytes, found 11",
     );
 }
+
+mod typed {
+    use super::*;
+    use shortguid::{IdPrefix, TypedShortGuid};
+
+    enum User {}
+
+    impl IdPrefix for User {
+        const PREFIX: &'static str = "user";
+    }
+
+    type UserId = TypedShortGuid<User>;
+
+    #[test]
+    fn test_serialize_readable_string() {
+        let id = UserId::try_parse("user_-RaMXs6yT6q2vzKb85-h5A").unwrap();
+        serde_test::assert_tokens(&id.readable(), &[Token::Str("user_-RaMXs6yT6q2vzKb85-h5A")]);
+    }
+
+    #[test]
+    fn test_serialize_non_human_readable() {
+        let uuid_bytes = b"F9168C5E-CEB2-4F";
+        let id = UserId::new(ShortGuid::from_slice(uuid_bytes).unwrap());
+        serde_test::assert_tokens(&id.compact(), &[Token::Bytes(uuid_bytes)]);
+    }
+
+    #[test]
+    fn test_de_failure() {
+        serde_test::assert_de_tokens_error::<Readable<UserId>>(
+            &[Token::Str("order_-RaMXs6yT6q2vzKb85-h5A")],
+            "ShortGuid parsing failed: Invalid ID prefix; expected \"user_\"",
+        );
+    }
+}
+
+mod typeid {
+    use super::*;
+    use shortguid::TypeId;
+
+    #[test]
+    fn test_serialize_string() {
+        let id = TypeId::try_parse("user_01h455vb4pex5vsknk084sn02q").unwrap();
+        serde_test::assert_tokens(
+            &id.clone().readable(),
+            &[Token::Str("user_01h455vb4pex5vsknk084sn02q")],
+        );
+        serde_test::assert_tokens(
+            &id.compact(),
+            &[Token::Str("user_01h455vb4pex5vsknk084sn02q")],
+        );
+    }
+
+    #[test]
+    fn test_de_failure() {
+        serde_test::assert_de_tokens_error::<Readable<TypeId>>(
+            &[Token::Str("user_8zzzzzzzzzzzzzzzzzzzzzzzzz")],
+            "ShortGuid parsing failed: Invalid TypeID: suffix exceeds 128 bits",
+        );
+    }
+}
+
+mod version {
+    use super::*;
+    use shortguid::{ShortGuidV4, ShortGuidV7};
+
+    #[test]
+    fn test_serialize_readable_string() {
+        let id = ShortGuidV4::try_parse("f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4").unwrap();
+        serde_test::assert_tokens(&id.readable(), &[Token::Str("-RaMXs6yT6q2vzKb85-h5A")]);
+    }
+
+    #[test]
+    fn test_de_failure() {
+        serde_test::assert_de_tokens_error::<Readable<ShortGuidV7>>(
+            &[Token::Str("-RaMXs6yT6q2vzKb85-h5A")],
+            "ShortGuid parsing failed: Invalid ID version; got version 4",
+        );
+        serde_test::assert_de_tokens_error::<Compact<ShortGuidV4>>(
+            &[Token::Bytes(&[0; 16])],
+            "ShortGuid parsing failed: Invalid ID; the nil value is not allowed",
+        );
+    }
+}
+
+mod non_nil {
+    use super::*;
+    use shortguid::NonNilShortGuid;
+
+    #[test]
+    fn test_serialize_readable_string() {
+        let id = NonNilShortGuid::try_parse("f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4").unwrap();
+        serde_test::assert_tokens(&id.readable(), &[Token::Str("-RaMXs6yT6q2vzKb85-h5A")]);
+    }
+
+    #[test]
+    fn test_de_failure() {
+        serde_test::assert_de_tokens_error::<Readable<NonNilShortGuid>>(
+            &[Token::Str("AAAAAAAAAAAAAAAAAAAAAA")],
+            "ShortGuid parsing failed: Invalid ID; the nil value is not allowed",
+        );
+    }
+}
+
+mod lenient {
+    use super::*;
+    use serde::Deserialize;
+
+    const UUID: &str = "c9a646d3-9c61-4cb7-bfcd-ee2522c8f633";
+    const VALUE: u128 = 0xc9a646d3_9c61_4cb7_bfcd_ee2522c8f633;
+
+    #[derive(Debug, Deserialize, Eq, PartialEq)]
+    struct Order {
+        #[serde(with = "shortguid::serde::lenient")]
+        id: ShortGuid,
+    }
+
+    #[derive(Debug, Deserialize, Eq, PartialEq)]
+    struct Envelope {
+        order: Order,
+    }
+
+    fn id() -> ShortGuid {
+        ShortGuid::try_parse(UUID).unwrap()
+    }
+
+    #[test]
+    fn test_de_tokens() {
+        const BYTES: [u8; 16] = VALUE.to_be_bytes();
+        for value in [
+            vec![Token::Str(UUID)],
+            vec![Token::Str("yaZG05xhTLe_ze4lIsj2Mw")],
+            vec![Token::Bytes(&BYTES)],
+            vec![
+                Token::Map { len: Some(2) },
+                Token::Str("lo"),
+                Token::U64(VALUE as u64),
+                Token::Str("hi"),
+                Token::U64((VALUE >> 64) as u64),
+                Token::MapEnd,
+            ],
+        ] {
+            let mut tokens = vec![
+                Token::Struct {
+                    name: "Order",
+                    len: 1,
+                },
+                Token::Str("id"),
+            ];
+            tokens.extend(value);
+            tokens.push(Token::StructEnd);
+            serde_test::assert_de_tokens(&Order { id: id() }.readable(), &tokens);
+        }
+    }
+
+    #[test]
+    fn test_de_json() {
+        let expected = Envelope {
+            order: Order { id: id() },
+        };
+        for json in [
+            format!(r#"{{"order": {{"id": "{UUID}"}}}}"#),
+            r#"{"order": {"id": "yaZG05xhTLe_ze4lIsj2Mw"}}"#.to_string(),
+            format!(r#"{{"order": {{"id": {:?}}}}}"#, id().as_bytes()),
+            format!(
+                r#"{{"order": {{"id": {{"hi": {}, "lo": {}}}}}}}"#,
+                VALUE >> 64,
+                VALUE as u64
+            ),
+        ] {
+            let envelope: Envelope = serde_json::from_str(&json).unwrap();
+            assert_eq!(envelope, expected, "{json}");
+        }
+
+        let small: Order = serde_json::from_str(r#"{"id": 42}"#).unwrap();
+        assert_eq!(small.id, ShortGuid::from_bytes(42u128.to_be_bytes()));
+    }
+
+    #[test]
+    fn test_de_failure() {
+        let err = serde_json::from_str::<Order>(r#"{"id": true}"#).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid type: boolean `true`, expected one of: a ShortGuid or UUID string, \
+             16 bytes, a sequence of 16 bytes, an unsigned 128-bit integer, or a map with \
+             \"hi\" and \"lo\" 64-bit halves at line 1 column 11"
+        );
+
+        let err = serde_json::from_str::<Order>(r#"{"id": {"hi": 1}}"#).unwrap_err();
+        assert_eq!(err.to_string(), "missing field `lo` at line 1 column 16");
+
+        let err = serde_json::from_str::<Order>(r#"{"id": {"hi": 1, "mid": 2}}"#).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown field `mid`, expected `hi` or `lo` at line 1 column 22"
+        );
+
+        let err = serde_json::from_str::<Order>(r#"{"id": -1}"#).unwrap_err();
+        assert!(err.to_string().starts_with("invalid value: integer `-1`"));
+
+        let json = format!(r#"{{"id": {VALUE}}}"#);
+        let err = serde_json::from_str::<Order>(&json).unwrap_err();
+        assert!(
+            err.to_string().starts_with("invalid type: floating point"),
+            "{err}"
+        );
+        assert!(
+            err.to_string().contains("must be sent as a string"),
+            "{err}"
+        );
+
+        serde_test::assert_de_tokens_error::<Readable<Order>>(
+            &[
+                Token::Struct {
+                    name: "Order",
+                    len: 1,
+                },
+                Token::Str("id"),
+                Token::F64(1.5),
+            ],
+            "invalid type: floating point `1.5`, expected an integer; integers beyond \
+             64 bits must be sent as a string or a map with \"hi\" and \"lo\" 64-bit halves",
+        );
+    }
+}
+
+mod with {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+    use std::collections::{BTreeMap, HashMap};
+
+    const UUID: &str = "c9a646d3-9c61-4cb7-bfcd-ee2522c8f633";
+    const SHORT: &str = "yaZG05xhTLe_ze4lIsj2Mw";
+    const VALUE: u128 = 0xc9a646d3_9c61_4cb7_bfcd_ee2522c8f633;
+    const BYTES: [u8; 16] = VALUE.to_be_bytes();
+
+    fn id() -> ShortGuid {
+        ShortGuid::try_parse(UUID).unwrap()
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Eq, PartialEq)]
+    struct Fields {
+        #[serde(with = "shortguid::serde::hyphenated")]
+        hyphenated: ShortGuid,
+        #[serde(with = "shortguid::serde::short")]
+        short: ShortGuid,
+        #[serde(with = "shortguid::serde::bytes_array")]
+        bytes: ShortGuid,
+        #[serde(with = "shortguid::serde::standard_base64")]
+        base64: ShortGuid,
+    }
+
+    fn fields() -> Fields {
+        Fields {
+            hyphenated: id(),
+            short: id(),
+            bytes: id(),
+            base64: id(),
+        }
+    }
+
+    fn tokens() -> Vec<Token> {
+        vec![
+            Token::Struct {
+                name: "Fields",
+                len: 4,
+            },
+            Token::Str("hyphenated"),
+            Token::Str(UUID),
+            Token::Str("short"),
+            Token::Str(SHORT),
+            Token::Str("bytes"),
+            Token::Bytes(&BYTES),
+            Token::Str("base64"),
+            Token::Str("yaZG05xhTLe/ze4lIsj2Mw=="),
+            Token::StructEnd,
+        ]
+    }
+
+    #[test]
+    fn test_representation_ignores_human_readable() {
+        serde_test::assert_tokens(&fields().readable(), &tokens());
+        serde_test::assert_tokens(&fields().compact(), &tokens());
+    }
+
+    #[test]
+    fn test_json() {
+        let json = serde_json::to_string(&fields()).unwrap();
+        assert_eq!(
+            json,
+            format!(
+                r#"{{"hyphenated":"{UUID}","short":"{SHORT}","bytes":{:?},"base64":"yaZG05xhTLe/ze4lIsj2Mw=="}}"#,
+                BYTES
+            )
+            .replace(' ', "")
+        );
+        assert_eq!(serde_json::from_str::<Fields>(&json).unwrap(), fields());
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Eq, PartialEq)]
+    struct Collections {
+        #[serde(with = "shortguid::serde::u128")]
+        number: ShortGuid,
+        #[serde(with = "shortguid::serde::hyphenated::option")]
+        some: Option<ShortGuid>,
+        #[serde(with = "shortguid::serde::hyphenated::option")]
+        none: Option<ShortGuid>,
+        #[serde(with = "shortguid::serde::u128::vec")]
+        list: Vec<ShortGuid>,
+        #[serde(with = "shortguid::serde::hyphenated::map")]
+        names: BTreeMap<ShortGuid, String>,
+        #[serde(with = "shortguid::serde::u128::map")]
+        counts: HashMap<ShortGuid, u32>,
+        #[serde(with = "shortguid::serde::lenient::vec")]
+        lenient: Vec<ShortGuid>,
+    }
+
+    #[test]
+    fn test_collections() {
+        let collections = Collections {
+            number: id(),
+            some: Some(id()),
+            none: None,
+            list: vec![id(), ShortGuid::default()],
+            names: BTreeMap::from([(id(), "order".to_string())]),
+            counts: HashMap::from([(id(), 3)]),
+            lenient: vec![id(), id()],
+        };
+
+        let json = serde_json::to_string(&collections).unwrap();
+        assert_eq!(
+            json,
+            format!(
+                r#"{{"number":{VALUE},"some":"{UUID}","none":null,"list":[{VALUE},0],"names":{{"{UUID}":"order"}},"counts":{{"{VALUE}":3}},"lenient":["{SHORT}","{SHORT}"]}}"#
+            )
+        );
+
+        let json = json.replacen(
+            &format!(r#""lenient":["{SHORT}""#),
+            &format!(r#""lenient":["{UUID}""#),
+            1,
+        );
+        assert_eq!(
+            serde_json::from_str::<Collections>(&json).unwrap(),
+            collections
+        );
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Eq, PartialEq)]
+    struct StringCollections {
+        #[serde(with = "shortguid::serde::lenient::option")]
+        lenient_option: Option<ShortGuid>,
+        #[serde(with = "shortguid::serde::lenient::map")]
+        lenient_map: BTreeMap<ShortGuid, u32>,
+        #[serde(with = "shortguid::serde::hyphenated::vec")]
+        hyphenated_vec: Vec<ShortGuid>,
+        #[serde(with = "shortguid::serde::short::option")]
+        short_option: Option<ShortGuid>,
+        #[serde(with = "shortguid::serde::short::vec")]
+        short_vec: Vec<ShortGuid>,
+        #[serde(with = "shortguid::serde::short::map")]
+        short_map: BTreeMap<ShortGuid, u32>,
+        #[serde(with = "shortguid::serde::u128::option")]
+        u128_option: Option<ShortGuid>,
+        #[serde(with = "shortguid::serde::standard_base64::option")]
+        base64_option: Option<ShortGuid>,
+        #[serde(with = "shortguid::serde::standard_base64::vec")]
+        base64_vec: Vec<ShortGuid>,
+        #[serde(with = "shortguid::serde::standard_base64::map")]
+        base64_map: BTreeMap<ShortGuid, u32>,
+    }
+
+    #[test]
+    fn test_string_collections_json() {
+        let map = BTreeMap::from([(id(), 1), (ShortGuid::default(), 2)]);
+        let collections = StringCollections {
+            lenient_option: Some(id()),
+            lenient_map: map.clone(),
+            hyphenated_vec: vec![id(), ShortGuid::default()],
+            short_option: Some(id()),
+            short_vec: vec![id()],
+            short_map: map.clone(),
+            u128_option: Some(id()),
+            base64_option: None,
+            base64_vec: vec![id()],
+            base64_map: map,
+        };
+
+        let json = serde_json::to_string(&collections).unwrap();
+        assert!(json.contains(&format!(
+            r#""short_map":{{"AAAAAAAAAAAAAAAAAAAAAA":2,"{SHORT}":1}}"#
+        )));
+        assert!(json.contains(
+            r#""base64_map":{"AAAAAAAAAAAAAAAAAAAAAA==":2,"yaZG05xhTLe/ze4lIsj2Mw==":1}"#
+        ));
+        assert_eq!(
+            serde_json::from_str::<StringCollections>(&json).unwrap(),
+            collections
+        );
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Eq, PartialEq)]
+    struct ByteCollections {
+        #[serde(with = "shortguid::serde::bytes_array::option")]
+        bytes_option: Option<ShortGuid>,
+        #[serde(with = "shortguid::serde::bytes_array::vec")]
+        bytes_vec: Vec<ShortGuid>,
+        #[serde(with = "shortguid::serde::bytes_array::map")]
+        bytes_map: BTreeMap<ShortGuid, u32>,
+        #[serde(with = "shortguid::serde::fixed_array::option")]
+        fixed_option: Option<ShortGuid>,
+        #[serde(with = "shortguid::serde::fixed_array::map")]
+        fixed_map: BTreeMap<ShortGuid, u32>,
+        #[serde(with = "shortguid::serde::u128::map")]
+        u128_map: BTreeMap<ShortGuid, u32>,
+    }
+
+    #[test]
+    fn test_byte_collections_postcard() {
+        let map = BTreeMap::from([(id(), 1), (ShortGuid::default(), 2)]);
+        let collections = ByteCollections {
+            bytes_option: Some(id()),
+            bytes_vec: vec![id(), ShortGuid::default()],
+            bytes_map: map.clone(),
+            fixed_option: None,
+            fixed_map: map.clone(),
+            u128_map: map,
+        };
+
+        let bytes = postcard::to_allocvec(&collections).unwrap();
+        assert_eq!(
+            postcard::from_bytes::<ByteCollections>(&bytes).unwrap(),
+            collections
+        );
+    }
+
+    #[test]
+    fn test_byte_map_keys_fail_in_json() {
+        #[derive(Debug, Serialize)]
+        struct BytesMap(
+            #[serde(with = "shortguid::serde::bytes_array::map")] BTreeMap<ShortGuid, u32>,
+        );
+
+        let map = BytesMap(BTreeMap::from([(id(), 1)]));
+        assert!(serde_json::to_string(&map).is_err());
+    }
+
+    #[test]
+    fn test_de_failure() {
+        #[derive(Debug, Deserialize, Eq, PartialEq)]
+        struct Hyphenated(#[serde(with = "shortguid::serde::hyphenated")] ShortGuid);
+
+        #[derive(Debug, Deserialize, Eq, PartialEq)]
+        struct Base64(#[serde(with = "shortguid::serde::standard_base64")] ShortGuid);
+
+        serde_test::assert_de_tokens_error::<Hyphenated>(
+            &[
+                Token::NewtypeStruct { name: "Hyphenated" },
+                Token::Str(SHORT),
+            ],
+            "ShortGuid parsing failed: Invalid UUID: failed to parse a UUID",
+        );
+        serde_test::assert_de_tokens_error::<Base64>(
+            &[Token::NewtypeStruct { name: "Base64" }, Token::Str(SHORT)],
+            "ShortGuid parsing failed: Invalid ID format: Invalid symbol 95, offset 11.",
+        );
+    }
+
+    #[test]
+    fn test_short_rejects_empty_string() {
+        #[derive(Debug, Deserialize, Eq, PartialEq)]
+        struct Short(#[serde(with = "shortguid::serde::short")] ShortGuid);
+
+        serde_test::assert_de_tokens_error::<Short>(
+            &[Token::NewtypeStruct { name: "Short" }, Token::Str("")],
+            "ShortGuid parsing failed: Invalid ID length; expected 22 characters, but got 0",
+        );
+    }
+}
+
+mod fixed_array {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+
+    const BYTES: [u8; 16] = *b"F9168C5E-CEB2-4F";
+
+    #[derive(Debug, Serialize, Deserialize, Eq, PartialEq)]
+    struct Fixed(#[serde(with = "shortguid::serde::fixed_array")] ShortGuid);
+
+    #[derive(Debug, Serialize, Deserialize, Eq, PartialEq)]
+    struct Record {
+        #[serde(with = "shortguid::serde::fixed_array")]
+        id: ShortGuid,
+        #[serde(with = "shortguid::serde::fixed_array::vec")]
+        parents: Vec<ShortGuid>,
+    }
+
+    #[derive(Debug, Deserialize, Eq, PartialEq)]
+    struct Legacy(
+        #[serde(deserialize_with = "shortguid::serde::fixed_array::deserialize_bytes")] ShortGuid,
+    );
+
+    fn id() -> ShortGuid {
+        ShortGuid::from_bytes(BYTES)
+    }
+
+    #[test]
+    fn test_tokens() {
+        let mut tokens = vec![
+            Token::NewtypeStruct { name: "Fixed" },
+            Token::Tuple { len: 16 },
+        ];
+        tokens.extend(BYTES.iter().map(|&b| Token::U8(b)));
+        tokens.push(Token::TupleEnd);
+        serde_test::assert_tokens(&Fixed(id()).compact(), &tokens);
+        serde_test::assert_tokens(&Fixed(id()).readable(), &tokens);
+
+        serde_test::assert_de_tokens(
+            &Fixed(id()).compact(),
+            &[Token::NewtypeStruct { name: "Fixed" }, Token::Bytes(&BYTES)],
+        );
+
+        let mut tokens = vec![
+            Token::NewtypeStruct { name: "Fixed" },
+            Token::Tuple { len: 17 },
+        ];
+        tokens.extend(BYTES.iter().chain(&[0]).map(|&b| Token::U8(b)));
+        tokens.push(Token::TupleEnd);
+        serde_test::assert_de_tokens_error::<Fixed>(
+            &tokens,
+            "invalid length 17, expected a tuple of 16 bytes",
+        );
+    }
+
+    #[test]
+    fn test_bincode_size() {
+        let config = bincode::config::legacy();
+        let regular = bincode::serde::encode_to_vec(id(), config).unwrap();
+        let fixed = bincode::serde::encode_to_vec(Fixed(id()), config).unwrap();
+        assert_eq!(regular.len(), 24);
+        assert_eq!(fixed, BYTES);
+
+        let (decoded, _): (Fixed, _) = bincode::serde::decode_from_slice(&fixed, config).unwrap();
+        assert_eq!(decoded, Fixed(id()));
+        let (decoded, _): (Legacy, _) =
+            bincode::serde::decode_from_slice(&regular, config).unwrap();
+        assert_eq!(decoded, Legacy(id()));
+
+        let config = bincode::config::standard();
+        let record = Record {
+            id: id(),
+            parents: vec![id(), id()],
+        };
+        let encoded = bincode::serde::encode_to_vec(&record, config).unwrap();
+        assert_eq!(encoded.len(), 16 + 1 + 2 * 16);
+        let (decoded, _): (Record, _) =
+            bincode::serde::decode_from_slice(&encoded, config).unwrap();
+        assert_eq!(decoded, record);
+    }
+
+    #[test]
+    fn test_postcard_size() {
+        let regular = postcard::to_allocvec(&id()).unwrap();
+        let fixed = postcard::to_allocvec(&Fixed(id())).unwrap();
+        assert_eq!(regular.len(), 17);
+        assert_eq!(fixed, BYTES);
+        assert_eq!(postcard::from_bytes::<Fixed>(&fixed).unwrap(), Fixed(id()));
+        assert_eq!(
+            postcard::from_bytes::<Legacy>(&regular).unwrap(),
+            Legacy(id())
+        );
+    }
+
+    #[test]
+    fn test_json_roundtrip() {
+        let json = serde_json::to_string(&Fixed(id())).unwrap();
+        assert_eq!(json, format!("{BYTES:?}").replace(' ', ""));
+        assert_eq!(serde_json::from_str::<Fixed>(&json).unwrap(), Fixed(id()));
+        assert_eq!(serde_json::from_str::<Legacy>(&json).unwrap(), Legacy(id()));
+
+        let json = json.replace(']', ",0]");
+        assert!(serde_json::from_str::<Fixed>(&json).is_err());
+    }
+}
+
+#[cfg(feature = "cbor")]
+mod cbor {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+
+    const UUID: &str = "c9a646d3-9c61-4cb7-bfcd-ee2522c8f633";
+
+    #[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+    struct Tagged(#[serde(with = "shortguid::serde::cbor")] ShortGuid);
+
+    #[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+    struct Record {
+        #[serde(with = "shortguid::serde::cbor::option")]
+        id: Option<ShortGuid>,
+        #[serde(with = "shortguid::serde::cbor::vec")]
+        parents: Vec<ShortGuid>,
+    }
+
+    fn to_cbor<T: Serialize>(value: &T) -> String {
+        let mut bytes = Vec::new();
+        ciborium::into_writer(value, &mut bytes).unwrap();
+        hex::encode(bytes)
+    }
+
+    fn from_cbor<T: for<'de> Deserialize<'de>>(value: &str) -> Result<T, String> {
+        let bytes = hex::decode(value).unwrap();
+        ciborium::from_reader(bytes.as_slice()).map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn test_reference_encoding() {
+        let id = ShortGuid::try_parse(UUID).unwrap();
+        // 37(h'c9a646d39c614cb7bfcdee2522c8f633')
+        let expected = "d82550c9a646d39c614cb7bfcdee2522c8f633";
+        assert_eq!(to_cbor(&Tagged(id)), expected);
+        assert_eq!(from_cbor::<Tagged>(expected).unwrap(), Tagged(id));
+
+        // The regular representation is an untagged byte string.
+        assert_eq!(to_cbor(&id), "50c9a646d39c614cb7bfcdee2522c8f633");
+    }
+
+    #[test]
+    fn test_untagged_input() {
+        let id = ShortGuid::try_parse(UUID).unwrap();
+        for cbor in [
+            // h'c9a646d39c614cb7bfcdee2522c8f633'
+            "50c9a646d39c614cb7bfcdee2522c8f633".to_string(),
+            // "yaZG05xhTLe_ze4lIsj2Mw"
+            format!("76{}", hex::encode("yaZG05xhTLe_ze4lIsj2Mw")),
+            // "c9a646d3-9c61-4cb7-bfcd-ee2522c8f633"
+            format!("7824{}", hex::encode(UUID)),
+        ] {
+            assert_eq!(from_cbor::<Tagged>(&cbor).unwrap(), Tagged(id), "{cbor}");
+        }
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        // 38(h'...'), i.e. the wrong tag.
+        assert!(from_cbor::<Tagged>("d82650c9a646d39c614cb7bfcdee2522c8f633").is_err());
+        // 37(h'c9a6'), i.e. too few bytes.
+        assert!(from_cbor::<Tagged>("d82542c9a6").is_err());
+        // 37(1)
+        assert!(from_cbor::<Tagged>("d82501").is_err());
+    }
+
+    #[test]
+    fn test_collections() {
+        let id = ShortGuid::try_parse(UUID).unwrap();
+        let record = Record {
+            id: Some(id),
+            parents: vec![id],
+        };
+        let cbor = to_cbor(&record);
+        assert_eq!(
+            cbor,
+            format!(
+                "a2626964d82550{hex}67706172656e747381d82550{hex}",
+                hex = hex::encode(id.as_bytes())
+            )
+        );
+        assert_eq!(from_cbor::<Record>(&cbor).unwrap(), record);
+
+        let empty = Record {
+            id: None,
+            parents: vec![],
+        };
+        assert_eq!(from_cbor::<Record>(&to_cbor(&empty)).unwrap(), empty);
+    }
+
+    #[test]
+    fn test_map() {
+        #[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+        struct Counts(
+            #[serde(with = "shortguid::serde::cbor::map")]
+            std::collections::BTreeMap<ShortGuid, u32>,
+        );
+
+        let id = ShortGuid::try_parse(UUID).unwrap();
+        let counts = Counts([(id, 3)].into());
+        let cbor = to_cbor(&counts);
+        // {37(h'...'): 3}
+        assert_eq!(cbor, format!("a1d82550{}03", hex::encode(id.as_bytes())));
+        assert_eq!(from_cbor::<Counts>(&cbor).unwrap(), counts);
+    }
+}
+
+#[cfg(feature = "bson")]
+mod bson_binary {
+    use super::*;
+    use bson::spec::BinarySubtype;
+    use bson::{doc, Binary, Bson};
+    use serde::{Deserialize, Serialize};
+
+    const UUID: &str = "c9a646d3-9c61-4cb7-bfcd-ee2522c8f633";
+
+    #[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+    struct Record {
+        #[serde(with = "shortguid::serde::bson_binary")]
+        id: ShortGuid,
+        #[serde(with = "shortguid::serde::bson_binary::option")]
+        parent: Option<ShortGuid>,
+    }
+
+    #[derive(Deserialize, Debug, Eq, PartialEq)]
+    struct Legacy {
+        #[serde(with = "shortguid::serde::bson_binary::csharp_legacy")]
+        csharp: ShortGuid,
+        #[serde(with = "shortguid::serde::bson_binary::java_legacy")]
+        java: ShortGuid,
+        #[serde(with = "shortguid::serde::bson_binary::python_legacy")]
+        python: ShortGuid,
+    }
+
+    fn binary(subtype: BinarySubtype, hex: &str) -> Bson {
+        Bson::Binary(Binary {
+            subtype,
+            bytes: hex::decode(hex).unwrap(),
+        })
+    }
+
+    #[test]
+    fn test_subtype() {
+        let id = ShortGuid::try_parse(UUID).unwrap();
+        let record = Record { id, parent: None };
+        let document = bson::to_document(&record).unwrap();
+        assert_eq!(
+            document,
+            doc! {
+                "id": binary(BinarySubtype::Uuid, "c9a646d39c614cb7bfcdee2522c8f633"),
+                "parent": Bson::Null,
+            }
+        );
+        assert_eq!(bson::from_document::<Record>(document).unwrap(), record);
+
+        // The regular representation is a string, since `bson` is human-readable by default.
+        assert_eq!(
+            bson::to_bson(&id).unwrap(),
+            Bson::String("yaZG05xhTLe_ze4lIsj2Mw".to_string())
+        );
+    }
+
+    #[test]
+    fn test_roundtrip_bytes() {
+        let id = ShortGuid::try_parse(UUID).unwrap();
+        let record = Record {
+            id,
+            parent: Some(id),
+        };
+        let bytes = bson::to_vec(&record).unwrap();
+        assert_eq!(bson::from_slice::<Record>(&bytes).unwrap(), record);
+    }
+
+    #[test]
+    fn test_legacy() {
+        let id = ShortGuid::try_parse(UUID).unwrap();
+        let document = doc! {
+            "csharp": binary(BinarySubtype::UuidOld, "d346a6c9619cb74cbfcdee2522c8f633"),
+            "java": binary(BinarySubtype::UuidOld, "b74c619cd346a6c933f6c82225eecdbf"),
+            "python": binary(BinarySubtype::UuidOld, "c9a646d39c614cb7bfcdee2522c8f633"),
+        };
+        let expected = Legacy {
+            csharp: id,
+            java: id,
+            python: id,
+        };
+        assert_eq!(bson::from_document::<Legacy>(document).unwrap(), expected);
+
+        // The legacy modules also read the subtype 4.
+        let standard = binary(BinarySubtype::Uuid, "c9a646d39c614cb7bfcdee2522c8f633");
+        let document = doc! {
+            "csharp": standard.clone(),
+            "java": standard.clone(),
+            "python": standard,
+        };
+        assert_eq!(bson::from_document::<Legacy>(document).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_collections() {
+        #[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+        struct Collections {
+            #[serde(with = "shortguid::serde::bson_binary::vec")]
+            ids: Vec<ShortGuid>,
+            #[serde(with = "shortguid::serde::bson_binary::csharp_legacy::option")]
+            csharp_option: Option<ShortGuid>,
+            #[serde(with = "shortguid::serde::bson_binary::csharp_legacy::vec")]
+            csharp_vec: Vec<ShortGuid>,
+            #[serde(with = "shortguid::serde::bson_binary::java_legacy::option")]
+            java_option: Option<ShortGuid>,
+            #[serde(with = "shortguid::serde::bson_binary::java_legacy::vec")]
+            java_vec: Vec<ShortGuid>,
+            #[serde(with = "shortguid::serde::bson_binary::python_legacy::option")]
+            python_option: Option<ShortGuid>,
+            #[serde(with = "shortguid::serde::bson_binary::python_legacy::vec")]
+            python_vec: Vec<ShortGuid>,
+        }
+
+        let id = ShortGuid::try_parse(UUID).unwrap();
+        let collections = Collections {
+            ids: vec![id, ShortGuid::default()],
+            csharp_option: Some(id),
+            csharp_vec: vec![id],
+            java_option: None,
+            java_vec: vec![id],
+            python_option: Some(id),
+            python_vec: vec![],
+        };
+        let document = bson::to_document(&collections).unwrap();
+        assert_eq!(
+            document.get_array("ids").unwrap()[0],
+            binary(BinarySubtype::Uuid, "c9a646d39c614cb7bfcdee2522c8f633")
+        );
+        assert_eq!(
+            bson::from_document::<Collections>(document).unwrap(),
+            collections
+        );
+
+        // The legacy collections also read the subtype 3.
+        let legacy = doc! {
+            "ids": [],
+            "csharp_option": binary(BinarySubtype::UuidOld, "d346a6c9619cb74cbfcdee2522c8f633"),
+            "csharp_vec": [binary(BinarySubtype::UuidOld, "d346a6c9619cb74cbfcdee2522c8f633")],
+            "java_option": Bson::Null,
+            "java_vec": [binary(BinarySubtype::UuidOld, "b74c619cd346a6c933f6c82225eecdbf")],
+            "python_option": binary(BinarySubtype::UuidOld, "c9a646d39c614cb7bfcdee2522c8f633"),
+            "python_vec": [],
+        };
+        let expected = Collections {
+            ids: vec![],
+            ..collections
+        };
+        assert_eq!(
+            bson::from_document::<Collections>(legacy).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_invalid_subtype() {
+        let document = doc! {
+            "id": binary(BinarySubtype::UuidOld, "c9a646d39c614cb7bfcdee2522c8f633"),
+            "parent": Bson::Null,
+        };
+        let error = bson::from_document::<Record>(document).unwrap_err();
+        assert!(
+            error.to_string().contains("invalid binary subtype 0x03"),
+            "{error}"
+        );
+
+        let document = doc! {
+            "id": binary(BinarySubtype::Generic, "c9a646d39c614cb7bfcdee2522c8f633"),
+            "parent": Bson::Null,
+        };
+        assert!(bson::from_document::<Record>(document).is_err());
+    }
+}